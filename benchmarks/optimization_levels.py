@@ -0,0 +1,55 @@
+#!/usr/bin/env python
+
+"""Build benchmarks with every optimization level and record their timings.
+
+For each benchmark `hyperfine` results are written to
+`<benchmark>/optimization_levels.csv` and `<benchmark>/optimization_levels.json`."""
+
+import argparse
+import os
+import subprocess
+
+BENCHMARKS = {
+    # Size of Hilbert matrix is passed through `N`
+    "linalg": {"N": "50"},
+    "factorial": {},
+    "series": {},
+}
+LEVELS = range(0, 4)
+
+parser = argparse.ArgumentParser(description=__doc__)
+parser.add_argument(
+    "benchmark", help="Benchmarks to run", nargs="*", default=list(BENCHMARKS)
+)
+parser.add_argument("--ppl", help="Path to ppl executable", default="ppl")
+parser.add_argument("--warmup", help="Number of warmup runs", default="3")
+args = parser.parse_args()
+
+root = os.path.dirname(os.path.abspath(__file__))
+for name in args.benchmark:
+    directory = os.path.join(root, name)
+    commands = []
+    for level in LEVELS:
+        output_dir = f"target/O{level}"
+        subprocess.run(
+            [args.ppl, "build", f"-O{level}", "--output-dir", output_dir],
+            cwd=directory,
+            check=True,
+        )
+        commands += ["--command-name", f"-O{level}", f"./{output_dir}/{name}.out"]
+
+    subprocess.run(
+        [
+            "hyperfine",
+            "--warmup",
+            args.warmup,
+            "--export-csv",
+            "optimization_levels.csv",
+            "--export-json",
+            "optimization_levels.json",
+            *commands,
+        ],
+        cwd=directory,
+        env={**os.environ, **BENCHMARKS[name]},
+        check=True,
+    )
@@ -162,6 +162,8 @@ pub struct FunctionDeclaration {
 
     /// Annotations for function
     pub annotations: Vec<Annotation>,
+    /// Documentation comment for function
+    pub docs: Option<String>,
 }
 
 impl Ranged for FunctionDeclaration {
@@ -189,6 +191,7 @@ impl Parse for FunctionDeclaration {
 
     /// Parse function declaration using lexer
     fn parse(context: &mut Context<impl Lexer>) -> Result<Self, Self::Err> {
+        let docs = context.parse_doc_comment();
         let keyword = context.consume_keyword::<"fn">()?;
 
         let mut generic_parameters = Vec::new();
@@ -246,6 +249,7 @@ impl Parse for FunctionDeclaration {
             body,
             implicit_return,
             annotations: vec![],
+            docs,
         })
     }
 }
@@ -301,6 +305,7 @@ mod tests {
                 annotations: vec![],
                 body: vec![],
                 implicit_return: false,
+                docs: None,
             }
         );
     }
@@ -322,11 +327,40 @@ mod tests {
                     Literal::Integer {
                         value: "1".into(),
                         offset: 11,
+                        suffix: None,
                     }
                     .into()
                 ),],
-                implicit_return: true
+                implicit_return: true,
+                docs: None,
             }
         );
     }
+
+    #[test]
+    fn function_with_comments_and_blank_lines_in_body() {
+        let func = "fn test:\n\tlet x = 1\n\t// comment\n\t\n\t\t// deeper comment\n\tx"
+            .parse::<FunctionDeclaration>()
+            .unwrap();
+        assert_eq!(func.body.len(), 2);
+    }
+
+    #[test]
+    fn function_with_docs() {
+        let func = "/// Sum of numbers\n///\n/// Returns `1`\nfn sum => 1"
+            .parse::<FunctionDeclaration>()
+            .unwrap();
+        assert_eq!(func.docs.as_deref(), Some("Sum of numbers\n\nReturns `1`"));
+
+        let stmt = "// Not docs\n//// Not docs too\n/// Docs\n@mangle_as(\"sum\")\nfn sum => 1"
+            .parse::<Statement>()
+            .unwrap();
+        let Statement::Declaration(crate::ast::Declaration::Function(func)) = stmt else {
+            panic!("expected function declaration");
+        };
+        assert_eq!(func.docs.as_deref(), Some("Docs"));
+
+        let func = "fn sum => 1".parse::<FunctionDeclaration>().unwrap();
+        assert_eq!(func.docs, None);
+    }
 }
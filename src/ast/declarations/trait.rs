@@ -21,6 +21,8 @@ pub struct TraitDeclaration {
     pub supertraits: Vec<TypeReference>,
     /// Associated functions
     pub functions: Vec<FunctionDeclaration>,
+    /// Documentation comment for trait
+    pub docs: Option<String>,
 }
 
 impl Ranged for TraitDeclaration {
@@ -47,6 +49,7 @@ impl Parse for TraitDeclaration {
 
     /// Parse trait declaration
     fn parse(context: &mut Context<impl Lexer>) -> Result<Self, Self::Err> {
+        let docs = context.parse_doc_comment();
         let keyword = context.consume_keyword::<"trait">()?;
 
         let name = context.consume_id()?;
@@ -67,6 +70,7 @@ impl Parse for TraitDeclaration {
             name,
             supertraits,
             functions,
+            docs,
         })
     }
 }
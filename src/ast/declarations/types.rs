@@ -29,6 +29,9 @@ impl Ranged for Member {
 
 /// Parse single or multiple members, if they are separated by comma
 pub fn parse_members(context: &mut Context<impl Lexer>) -> Result<Vec<Member>, ParseError> {
+    // Documentation of members isn't kept
+    context.parse_doc_comment();
+
     let names = context.parse_comma_separated(|context| context.consume_id());
 
     context.lexer.consume(Token::Colon)?;
@@ -72,6 +75,18 @@ impl Parse for GenericParameter {
     }
 }
 
+impl Ranged for GenericParameter {
+    fn start(&self) -> usize {
+        self.name.start()
+    }
+
+    fn end(&self) -> usize {
+        self.constraint
+            .as_ref()
+            .map_or_else(|| self.name.end(), |c| c.end())
+    }
+}
+
 /// Declaration of type
 #[derive(Debug, PartialEq, Eq, AST, Clone)]
 pub struct TypeDeclaration {
@@ -85,6 +100,8 @@ pub struct TypeDeclaration {
     pub generic_parameters: Vec<GenericParameter>,
     /// Members of type
     pub members: Vec<Member>,
+    /// Documentation comment for type
+    pub docs: Option<String>,
 }
 
 impl Ranged for TypeDeclaration {
@@ -112,6 +129,7 @@ impl Parse for TypeDeclaration {
 
     /// Parse type declaration using lexer
     fn parse(context: &mut Context<impl Lexer>) -> Result<Self, Self::Err> {
+        let docs = context.parse_doc_comment();
         let keyword = context.consume_keyword::<"type">()?;
 
         let name = context.consume_id()?;
@@ -140,6 +158,7 @@ impl Parse for TypeDeclaration {
             name,
             generic_parameters,
             members,
+            docs,
         })
     }
 }
@@ -160,6 +179,7 @@ mod tests {
                 name: Identifier::from("x").at(5),
                 generic_parameters: vec![],
                 members: vec![],
+                docs: None,
             }
         );
     }
@@ -184,6 +204,7 @@ mod tests {
                         generic_parameters: Vec::new(),
                     },
                 },],
+                docs: None,
             }
         );
 
@@ -210,6 +231,7 @@ mod tests {
                         generic_parameters: Vec::new(),
                     },
                 },],
+                docs: None,
             }
         )
     }
@@ -241,6 +263,7 @@ mod tests {
                         ty: ty.clone(),
                     },
                 ],
+                docs: None,
             }
         );
     }
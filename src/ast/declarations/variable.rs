@@ -98,7 +98,8 @@ fn test_variable_declaration() {
             ty: None,
             initializer: Literal::Integer {
                 offset: 8,
-                value: "1".to_string()
+                value: "1".to_string(),
+                suffix: None
             }
             .into(),
             mutability: Mutability::Immutable,
@@ -114,7 +115,8 @@ fn test_variable_declaration() {
             ty: None,
             initializer: Literal::Integer {
                 offset: 12,
-                value: "1".to_string()
+                value: "1".to_string(),
+                suffix: None
             }
             .into(),
             mutability: Mutability::Mutable,
@@ -133,7 +135,8 @@ fn test_variable_declaration() {
             }),
             initializer: Literal::Integer {
                 offset: 17,
-                value: "1".to_string()
+                value: "1".to_string(),
+                suffix: None
             }
             .into(),
             mutability: Mutability::Immutable,
@@ -154,7 +157,8 @@ fn test_variable_declaration() {
             }),
             initializer: Literal::Integer {
                 offset: 21,
-                value: "1".to_string()
+                value: "1".to_string(),
+                suffix: None
             }
             .into(),
             mutability: Mutability::Mutable,
@@ -176,7 +176,8 @@ mod tests {
                         name: Identifier::from("x").at(7).into(),
                         value: Literal::Integer {
                             offset: 10,
-                            value: "0".to_string()
+                            value: "0".to_string(),
+                            suffix: None
                         }
                         .into()
                     },
@@ -184,7 +185,8 @@ mod tests {
                         name: Identifier::from("y").at(13).into(),
                         value: Literal::Integer {
                             offset: 16,
-                            value: "0".to_string()
+                            value: "0".to_string(),
+                            suffix: None
                         }
                         .into()
                     },
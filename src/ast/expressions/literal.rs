@@ -10,10 +10,18 @@ pub enum Literal {
     None { offset: usize },
     /// Bool literal
     Bool { offset: usize, value: bool },
-    /// Any precision decimal integer literal
-    Integer { offset: usize, value: String },
-    /// Any precision decimal rational literal
-    Rational { offset: usize, value: String },
+    /// Any precision decimal integer literal with optional type suffix
+    Integer {
+        offset: usize,
+        value: String,
+        suffix: Option<String>,
+    },
+    /// Any precision decimal rational literal with optional type suffix
+    Rational {
+        offset: usize,
+        value: String,
+        suffix: Option<String>,
+    },
     /// String literal
     String { offset: usize, value: String },
 }
@@ -51,20 +59,34 @@ impl Parse for Literal {
 
         let offset = context.lexer.span().start;
 
+        // Split literal like `1i8` into value and type suffix
+        let split_suffix = |literal: &str| match literal.find(['i', 'u', 'f']) {
+            Some(i) => (literal[..i].to_string(), Some(literal[i..].to_string())),
+            None => (literal.to_string(), None),
+        };
+
         Ok(match token {
             Token::None => Literal::None { offset },
             Token::False | Token::True => Literal::Bool {
                 offset,
                 value: token == Token::True,
             },
-            Token::Integer => Literal::Integer {
-                offset,
-                value: context.lexer.slice().to_string(),
-            },
-            Token::Rational => Literal::Rational {
-                offset,
-                value: context.lexer.slice().to_string(),
-            },
+            Token::Integer => {
+                let (value, suffix) = split_suffix(context.lexer.slice());
+                Literal::Integer {
+                    offset,
+                    value,
+                    suffix,
+                }
+            }
+            Token::Rational => {
+                let (value, suffix) = split_suffix(context.lexer.slice());
+                Literal::Rational {
+                    offset,
+                    value,
+                    suffix,
+                }
+            }
             Token::String => Literal::String {
                 offset,
                 value: context.lexer.slice()[1..context.lexer.span().len() - 1].to_string(),
@@ -81,8 +103,16 @@ impl Ranged for Literal {
         match self {
             Literal::None { offset } => *offset..*offset + "none".len(),
             Literal::Bool { offset, value } => *offset..*offset + format!("{}", value).len(),
-            Literal::Integer { offset, value } => *offset..*offset + value.len(),
-            Literal::Rational { offset, value } => *offset..*offset + value.len(),
+            Literal::Integer {
+                offset,
+                value,
+                suffix,
+            }
+            | Literal::Rational {
+                offset,
+                value,
+                suffix,
+            } => *offset..*offset + value.len() + suffix.as_ref().map_or(0, String::len),
             Literal::String { offset, value } => *offset..*offset + value.len() + 2,
         }
     }
@@ -122,7 +152,31 @@ fn test_integer() {
         literal,
         Literal::Integer {
             offset: 0,
-            value: "123".to_string()
+            value: "123".to_string(),
+            suffix: None
+        }
+    );
+}
+
+#[test]
+fn test_suffix() {
+    let literal = "123i8".parse::<Literal>().unwrap();
+    assert_eq!(
+        literal,
+        Literal::Integer {
+            offset: 0,
+            value: "123".to_string(),
+            suffix: Some("i8".to_string())
+        }
+    );
+
+    let literal = "1.5f32".parse::<Literal>().unwrap();
+    assert_eq!(
+        literal,
+        Literal::Rational {
+            offset: 0,
+            value: "1.5".to_string(),
+            suffix: Some("f32".to_string())
         }
     );
 }
@@ -0,0 +1,806 @@
+use std::ops::Range;
+
+use logos::Logos;
+
+use crate::{
+    mutability::Mutable,
+    syntax::{error::ParseError, Ranged, Token},
+    ErrVec,
+};
+
+use super::{
+    Annotation, Call, CallNamePart, Declaration, Expression, FnKind, FunctionDeclaration,
+    FunctionNamePart, GenericParameter, If, Member, Module, Statement, TraitDeclaration,
+    TypeDeclaration, TypeReference, Typename,
+};
+
+/// Replace indentation with spaces by tabs.
+///
+/// Width of indentation level is the smallest number of leading spaces in the source.
+/// Lines with only whitespace become empty
+///
+/// # Example
+/// ```
+/// use ppl::ast::normalize_indentation;
+///
+/// assert_eq!(
+///     normalize_indentation("loop:\n  if x:\n    y\n  \n"),
+///     "loop:\n\tif x:\n\t\ty\n\n"
+/// );
+/// ```
+pub fn normalize_indentation(source: &str) -> String {
+    let indentation = |line: &str| line.len() - line.trim_start_matches(['\t', ' ']).len();
+    let width = source
+        .split('\n')
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line[..indentation(line)].matches(' ').count())
+        .filter(|spaces| *spaces > 0)
+        .min()
+        .unwrap_or(1);
+
+    source
+        .split('\n')
+        .map(|line| {
+            if line.trim().is_empty() {
+                return String::new();
+            }
+
+            let (indent, rest) = line.split_at(indentation(line));
+            let levels = indent.matches('\t').count() + indent.matches(' ').count() / width;
+            "\t".repeat(levels) + rest
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Format PPL source code.
+///
+/// Indentation is normalized with [`normalize_indentation`] before parsing,
+/// so offsets of errors refer to the normalized source.
+///
+/// # Example
+/// ```
+/// use ppl::ast::format_code;
+///
+/// assert_eq!(
+///     format_code("fn  double <x:Integer>  => x*2 // twice\n").unwrap(),
+///     "fn double <x: Integer> => x * 2 // twice\n"
+/// );
+/// ```
+pub fn format_code(source: &str) -> Result<String, ErrVec<ParseError>> {
+    let source = normalize_indentation(source);
+    let module: Module = source.parse()?;
+
+    let mut formatter = SourceFormatter::new(&source);
+    formatter.items(&module.statements);
+    formatter.remaining_comments();
+    Ok(formatter.output)
+}
+
+/// Ranges of comments and offsets of code tokens in source
+fn comments_and_code(source: &str) -> (Vec<Range<usize>>, Vec<usize>) {
+    // Comments, other than documentation ones, are skipped by lexer,
+    // so they may only be between tokens
+    let comment_in = |gap: Range<usize>| {
+        source[gap.clone()]
+            .find("//")
+            .map(|start| gap.start + start..gap.start + source[gap.clone()].trim_end().len())
+    };
+
+    let mut comments = vec![];
+    let mut code = vec![];
+    let mut lexer = Token::lexer(source);
+    let mut end = 0;
+    while let Some(token) = lexer.next() {
+        let span = lexer.span();
+        comments.extend(comment_in(end..span.start));
+        match token {
+            Ok(Token::DocComment) => {
+                comments.push(span.start..span.start + lexer.slice().trim_end().len())
+            }
+            Ok(Token::Newline | Token::Tab) => {}
+            _ => code.push(span.start),
+        }
+        end = span.end;
+    }
+    comments.extend(comment_in(end..source.len()));
+
+    (comments, code)
+}
+
+/// Writes formatted code, keeping comments of the source
+struct SourceFormatter<'s> {
+    /// Source code with normalized indentation
+    source: &'s str,
+    /// Offsets of line starts
+    line_starts: Vec<usize>,
+    /// Comments of the source
+    comments: Vec<Range<usize>>,
+    /// Index of the first comment, that isn't written yet
+    next_comment: usize,
+    /// Offsets of code tokens
+    code: Vec<usize>,
+    /// Current indentation level
+    indentation: usize,
+    /// Source line of the last written line, if any in current block
+    last_line: Option<usize>,
+    /// Formatted code
+    output: String,
+}
+
+impl<'s> SourceFormatter<'s> {
+    /// Create formatter for source code
+    fn new(source: &'s str) -> Self {
+        let (comments, code) = comments_and_code(source);
+        Self {
+            source,
+            line_starts: std::iter::once(0)
+                .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+                .collect(),
+            comments,
+            next_comment: 0,
+            code,
+            indentation: 0,
+            last_line: None,
+            output: String::new(),
+        }
+    }
+
+    /// Source line of offset
+    fn line(&self, offset: usize) -> usize {
+        self.line_starts.partition_point(|start| *start <= offset) - 1
+    }
+
+    /// Comment, that isn't written yet
+    fn pending_comment(&self) -> Option<Range<usize>> {
+        self.comments.get(self.next_comment).cloned()
+    }
+
+    /// Write string
+    fn write(&mut self, s: &str) {
+        self.output.push_str(s);
+    }
+
+    /// Write token as it is in the source
+    fn token(&mut self, range: Range<usize>) {
+        self.output.push_str(&self.source[range]);
+    }
+
+    /// Write items, separated by `separator`
+    fn separated<T>(&mut self, items: &[T], separator: &str, write: impl Fn(&mut Self, &T)) {
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 {
+                self.write(separator);
+            }
+            write(self, item);
+        }
+    }
+
+    /// Keep a single empty line, if there was any before `line` in the source
+    fn separate(&mut self, line: usize) {
+        if self.last_line.is_some_and(|last| line > last + 1) {
+            self.write("\n");
+        }
+    }
+
+    /// Start new line with current indentation
+    fn begin_line(&mut self) {
+        self.output.push_str(&"\t".repeat(self.indentation));
+    }
+
+    /// End line, that starts at `offset` in the source, with its trailing comment, if any
+    fn end_line(&mut self, offset: usize) {
+        let line = self.line(offset);
+        if let Some(comment) = self.pending_comment()
+            && comment.start >= offset
+            && self.line(comment.start) == line
+        {
+            self.write(" ");
+            self.token(comment);
+            self.next_comment += 1;
+        }
+        self.write("\n");
+        self.last_line = Some(line);
+    }
+
+    /// Write pending comment on its own line
+    fn comment_line(&mut self) {
+        let comment = self.pending_comment().unwrap();
+        let line = self.line(comment.start);
+        self.separate(line);
+        self.begin_line();
+        self.token(comment);
+        self.write("\n");
+        self.last_line = Some(line);
+        self.next_comment += 1;
+    }
+
+    /// Write comments, that are before `offset` in the source
+    fn comments_before(&mut self, offset: usize) {
+        while self
+            .pending_comment()
+            .is_some_and(|comment| comment.start < offset)
+        {
+            self.comment_line();
+        }
+    }
+
+    /// Write all comments, that are left
+    fn remaining_comments(&mut self) {
+        while self.pending_comment().is_some() {
+            self.comment_line();
+        }
+    }
+
+    /// Write items of a block at current indentation level
+    fn items(&mut self, items: &[impl FormatLines]) {
+        for item in items {
+            let start = item.first_offset();
+            self.comments_before(start);
+            self.separate(self.line(start));
+            item.format(self);
+        }
+    }
+
+    /// Write indented block of items
+    fn block(&mut self, items: &[impl FormatLines]) {
+        self.indentation += 1;
+        self.last_line = None;
+
+        self.items(items);
+
+        // Comments after the last item belong to the block, while they are indented
+        let next_line = self.last_line.map_or(0, |line| line + 1);
+        let code_after = self
+            .line_starts
+            .get(next_line)
+            .map(|start| self.code.partition_point(|offset| offset < start))
+            .and_then(|i| self.code.get(i).copied())
+            .unwrap_or(usize::MAX);
+        while let Some(comment) = self.pending_comment()
+            && comment.start < code_after
+            && self.source[self.line_starts[self.line(comment.start)]..]
+                .starts_with(&"\t".repeat(self.indentation))
+        {
+            self.comment_line();
+        }
+
+        self.indentation -= 1;
+    }
+
+    /// Write annotations, each on its own line
+    fn annotations(&mut self, annotations: &[Annotation]) {
+        for annotation in annotations {
+            self.begin_line();
+            self.write("@");
+            self.token(annotation.name.range());
+            if !annotation.args.is_empty() {
+                self.write("(");
+                self.separated(&annotation.args, ", ", Self::expression);
+                self.write(")");
+            }
+            self.end_line(annotation.name.start());
+        }
+    }
+
+    /// Write generic parameters, like `<T: Printable, U>`
+    fn generic_parameters(&mut self, parameters: &[GenericParameter]) {
+        if parameters.is_empty() {
+            return;
+        }
+
+        self.write("<");
+        self.separated(parameters, ", ", |f, parameter| {
+            f.token(parameter.name.range());
+            if let Some(constraint) = &parameter.constraint {
+                f.write(": ");
+                f.type_reference(constraint);
+            }
+        });
+        self.write(">");
+    }
+
+    /// Write type reference
+    fn type_reference(&mut self, ty: &TypeReference) {
+        match &ty.name {
+            Typename::Identifier(name) => {
+                self.token(name.range());
+                if !ty.generic_parameters.is_empty() {
+                    self.write("<");
+                    self.separated(&ty.generic_parameters, ", ", Self::type_reference);
+                    self.write(">");
+                }
+            }
+            Typename::Reference { mutable, .. } => {
+                self.write("&");
+                if mutable.is_some() {
+                    self.write("mut ");
+                }
+                self.type_reference(&ty.generic_parameters[0]);
+            }
+        }
+    }
+
+    /// Is there a space between adjacent name parts?
+    ///
+    /// Brackets keep spacing of the source,
+    /// because `a[i]` and `a [i]` are parsed differently
+    fn space_between(&self, left: Range<usize>, right: Range<usize>) -> bool {
+        let is_bracket = |range: Range<usize>| matches!(&self.source[range], "[" | "]");
+        if is_bracket(left.clone()) || is_bracket(right.clone()) {
+            left.end != right.start
+        } else {
+            true
+        }
+    }
+
+    /// Write expression
+    fn expression(&mut self, expression: &Expression) {
+        match expression {
+            Expression::Literal(literal) => self.token(literal.range()),
+            Expression::VariableReference(var) => self.token(var.name.range()),
+            Expression::TypeReference(ty) => self.type_reference(ty),
+            Expression::Call(call) => self.call(call),
+            Expression::Tuple(tuple) => {
+                self.write("(");
+                self.separated(&tuple.expressions, ", ", Self::expression);
+                self.write(")");
+            }
+            Expression::MemberReference(member) => {
+                self.expression(&member.base);
+                self.write(".");
+                self.token(member.name.range());
+            }
+            Expression::Constructor(constructor) => {
+                self.type_reference(&constructor.ty);
+                if constructor.initializers.is_empty() {
+                    self.write(" {}");
+                    return;
+                }
+
+                self.write(" { ");
+                self.separated(&constructor.initializers, ", ", |f, initializer| {
+                    if let Some(name) = &initializer.name {
+                        f.token(name.range());
+                        f.write(": ");
+                    }
+                    f.expression(&initializer.value);
+                });
+                self.write(" }");
+            }
+        }
+    }
+
+    /// Write function call
+    fn call(&mut self, call: &Call) {
+        let part = |f: &mut Self, part: &CallNamePart| match part {
+            CallNamePart::Text(text) => f.token(text.range()),
+            CallNamePart::Argument(arg) => f.expression(arg),
+        };
+
+        match (call.kind, call.name_parts.as_slice()) {
+            // Function without parameters, like `f()`
+            (FnKind::Function, [name]) => {
+                part(self, name);
+                self.write("()");
+            }
+            (FnKind::Operator, [left, operator, right]) => {
+                part(self, left);
+                self.write(" ");
+                part(self, operator);
+                self.write(" ");
+                part(self, right);
+            }
+            // Prefix and postfix operators are attached to their operands
+            (FnKind::Operator, parts) => {
+                for p in parts {
+                    part(self, p);
+                }
+            }
+            (FnKind::Function, parts) => {
+                for (i, p) in parts.iter().enumerate() {
+                    if i > 0 && self.space_between(parts[i - 1].range(), p.range()) {
+                        self.write(" ");
+                    }
+                    part(self, p);
+                }
+            }
+        }
+    }
+}
+
+/// Node, that is written on its own lines
+trait FormatLines {
+    /// Offset of the first token of the node, including annotations
+    fn first_offset(&self) -> usize;
+
+    /// Write node with indentation. Every written line is ended
+    fn format(&self, f: &mut SourceFormatter);
+}
+
+impl FormatLines for Statement {
+    fn first_offset(&self) -> usize {
+        let annotations = match self {
+            Statement::Declaration(Declaration::Function(f)) => f.annotations.as_slice(),
+            Statement::Declaration(Declaration::Type(ty)) => ty.annotations.as_slice(),
+            _ => &[],
+        };
+        annotations
+            .first()
+            .map_or_else(|| self.start(), |a| a.name.start())
+    }
+
+    fn format(&self, f: &mut SourceFormatter) {
+        match self {
+            Statement::Declaration(Declaration::Function(function)) => function.format(f),
+            Statement::Declaration(Declaration::Type(ty)) => ty.format(f),
+            Statement::Declaration(Declaration::Trait(tr)) => tr.format(f),
+            Statement::Declaration(Declaration::Variable(var)) => {
+                f.begin_line();
+                f.write("let ");
+                if var.is_mutable() {
+                    f.write("mut ");
+                }
+                f.token(var.name.range());
+                if let Some(ty) = &var.ty {
+                    f.write(": ");
+                    f.type_reference(ty);
+                }
+                f.write(" = ");
+                f.expression(&var.initializer);
+                f.end_line(var.start());
+            }
+            Statement::Expression(expr) => {
+                f.begin_line();
+                f.expression(expr);
+                f.end_line(expr.start());
+            }
+            Statement::Assignment(assignment) => {
+                f.begin_line();
+                f.expression(&assignment.target);
+                f.write(" = ");
+                f.expression(&assignment.value);
+                f.end_line(assignment.start());
+            }
+            Statement::Return(ret) => {
+                f.begin_line();
+                f.write("return");
+                if let Some(value) = &ret.value {
+                    f.write(" ");
+                    f.expression(value);
+                }
+                f.end_line(ret.start());
+            }
+            Statement::If(stmt) => stmt.format(f),
+            Statement::Loop(stmt) => {
+                f.begin_line();
+                f.write("loop:");
+                f.end_line(stmt.start());
+                f.block(&stmt.body);
+            }
+            Statement::While(stmt) => {
+                f.begin_line();
+                f.write("while ");
+                f.expression(&stmt.condition);
+                f.write(":");
+                f.end_line(stmt.start());
+                f.block(&stmt.body);
+            }
+            Statement::Use(stmt) => {
+                f.begin_line();
+                f.write("use ");
+                f.separated(&stmt.path, ".", |f, part| f.token(part.range()));
+                f.end_line(stmt.start());
+            }
+        }
+    }
+}
+
+impl FormatLines for If {
+    fn first_offset(&self) -> usize {
+        self.start()
+    }
+
+    fn format(&self, f: &mut SourceFormatter) {
+        f.begin_line();
+        f.write("if ");
+        f.expression(&self.condition);
+        f.write(":");
+        f.end_line(self.start());
+        f.block(&self.body);
+
+        for else_if in &self.else_ifs {
+            f.comments_before(else_if.start());
+            f.separate(f.line(else_if.start()));
+            f.begin_line();
+            f.write("else if ");
+            f.expression(&else_if.condition);
+            f.write(":");
+            f.end_line(else_if.start());
+            f.block(&else_if.body);
+        }
+
+        if let Some(else_block) = &self.else_block {
+            f.comments_before(else_block.start());
+            f.separate(f.line(else_block.start()));
+            f.begin_line();
+            f.write("else:");
+            f.end_line(else_block.start());
+            f.block(&else_block.body);
+        }
+    }
+}
+
+impl FormatLines for FunctionDeclaration {
+    fn first_offset(&self) -> usize {
+        self.annotations
+            .first()
+            .map_or_else(|| self.start(), |a| a.name.start())
+    }
+
+    fn format(&self, f: &mut SourceFormatter) {
+        f.annotations(&self.annotations);
+
+        f.begin_line();
+        f.write("fn");
+        f.generic_parameters(&self.generic_parameters);
+        for (i, part) in self.name_parts.iter().enumerate() {
+            if i == 0 || f.space_between(self.name_parts[i - 1].range(), part.range()) {
+                f.write(" ");
+            }
+            match part {
+                FunctionNamePart::Text(text) => f.token(text.range()),
+                FunctionNamePart::Parameter(parameter) => {
+                    f.write("<");
+                    if !parameter.name.is_empty() {
+                        f.token(parameter.name.range());
+                        f.write(": ");
+                    } else {
+                        f.write(":");
+                    }
+                    f.type_reference(&parameter.ty);
+                    f.write(">");
+                }
+            }
+        }
+
+        if let Some(return_type) = &self.return_type {
+            f.write(" -> ");
+            f.type_reference(return_type);
+        }
+
+        if self.implicit_return {
+            let Statement::Expression(expr) = &self.body[0] else {
+                unreachable!("implicit return of non-expression")
+            };
+            f.write(" => ");
+            f.expression(expr);
+            f.end_line(self.start());
+        } else if !self.body.is_empty() {
+            f.write(":");
+            f.end_line(self.start());
+            f.block(&self.body);
+        } else {
+            f.end_line(self.start());
+        }
+    }
+}
+
+/// Members of type with the same type reference, like `x, y: Integer`
+struct MembersGroup<'m>(&'m [Member]);
+
+impl FormatLines for MembersGroup<'_> {
+    fn first_offset(&self) -> usize {
+        self.0[0].start()
+    }
+
+    fn format(&self, f: &mut SourceFormatter) {
+        f.begin_line();
+        f.separated(self.0, ", ", |f, member| f.token(member.name.range()));
+        f.write(": ");
+        f.type_reference(&self.0[0].ty);
+        f.end_line(self.first_offset());
+    }
+}
+
+impl FormatLines for TypeDeclaration {
+    fn first_offset(&self) -> usize {
+        self.annotations
+            .first()
+            .map_or_else(|| self.start(), |a| a.name.start())
+    }
+
+    fn format(&self, f: &mut SourceFormatter) {
+        f.annotations(&self.annotations);
+
+        f.begin_line();
+        f.write("type ");
+        f.token(self.name.range());
+        f.generic_parameters(&self.generic_parameters);
+
+        if self.members.is_empty() {
+            f.end_line(self.start());
+            return;
+        }
+
+        f.write(":");
+        f.end_line(self.start());
+
+        // Members, declared together, share the same type reference
+        let groups: Vec<_> = self
+            .members
+            .chunk_by(|a, b| a.ty == b.ty)
+            .map(MembersGroup)
+            .collect();
+        f.block(&groups);
+    }
+}
+
+impl FormatLines for TraitDeclaration {
+    fn first_offset(&self) -> usize {
+        self.start()
+    }
+
+    fn format(&self, f: &mut SourceFormatter) {
+        f.begin_line();
+        f.write("trait ");
+        f.token(self.name.range());
+        f.write(":");
+        if !self.supertraits.is_empty() {
+            f.write(" ");
+            f.separated(&self.supertraits, " & ", SourceFormatter::type_reference);
+        }
+        f.end_line(self.start());
+        f.block(&self.functions);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use pretty_assertions::assert_eq;
+
+    use crate::ast::Module;
+
+    use super::{comments_and_code, format_code, normalize_indentation};
+
+    /// Fields of AST nodes, that store positions of tokens in source
+    const POSITIONS: [&str; 7] = [
+        "offset", "lparen", "rparen", "lbrace", "rbrace", "less", "greater",
+    ];
+
+    /// Debug representation of AST with positions of tokens cleared.
+    ///
+    /// Only position fields are cleared,
+    /// so any other change in the tree is still detected
+    fn without_offsets(module: &Module) -> String {
+        let debug = format!("{module:#?}");
+        let mut result = String::with_capacity(debug.len());
+        for line in debug.lines() {
+            let field = line.trim_start().split(": ").next().unwrap();
+            if POSITIONS.contains(&field) && line.ends_with(',') {
+                let indent = line.len() - line.trim_start().len();
+                result.push_str(&line[..indent]);
+                result.push_str(field);
+                result.push_str(": _,");
+            } else {
+                result.push_str(line);
+            }
+            result.push('\n');
+        }
+        result
+    }
+
+    /// Text of comments in source
+    fn comments(source: &str) -> Vec<String> {
+        comments_and_code(source)
+            .0
+            .into_iter()
+            .map(|range| source[range].to_string())
+            .collect()
+    }
+
+    #[test]
+    fn normalize_spacing() {
+        assert_eq!(
+            format_code("let   mut x:Integer=1+2*3\nx=(x,x)\nprintln  -x").unwrap(),
+            "let mut x: Integer = 1 + 2 * 3\nx = (x, x)\nprintln -x\n"
+        );
+        assert_eq!(
+            format_code("type   Point<T>:\n\tx,y:T\n\tname:String").unwrap(),
+            "type Point<T>:\n\tx, y: T\n\tname: String\n"
+        );
+        assert_eq!(
+            format_code("@mangle_as(\"f\")\nfn<T:Printable>f<x:T>->T:\n\treturn x").unwrap(),
+            "@mangle_as(\"f\")\nfn<T: Printable> f <x: T> -> T:\n\treturn x\n"
+        );
+        assert_eq!(
+            format_code("fn<T> <:Type<T>>[] => T[]\nlet y = a[0]+Point{x:1,y}").unwrap(),
+            "fn<T> <:Type<T>>[] => T[]\nlet y = a[0] + Point { x: 1, y }\n"
+        );
+    }
+
+    #[test]
+    fn keep_comments_and_empty_lines() {
+        let source = "// header\n\n\n\nfn f: // trailing\n\t// inside\n\n\tif x:\n\t\ty\n\t\t// end of if\n\t// before else\n\telse:\n\t\tz\n\t// end of f\n// end\n";
+        let expected = "// header\n\nfn f: // trailing\n\t// inside\n\n\tif x:\n\t\ty\n\t\t// end of if\n\t// before else\n\telse:\n\t\tz\n\t// end of f\n// end\n";
+        assert_eq!(format_code(source).unwrap(), expected);
+    }
+
+    #[test]
+    fn replace_spaces_with_tabs() {
+        assert_eq!(
+            normalize_indentation("loop:\n    if x:\n        y\n    z"),
+            "loop:\n\tif x:\n\t\ty\n\tz"
+        );
+        assert_eq!(
+            format_code("loop:\n  println \"Spaces\"\n").unwrap(),
+            "loop:\n\tprintln \"Spaces\"\n"
+        );
+    }
+
+    #[test]
+    fn positions_are_the_only_ignored_difference() {
+        let parse = |source: &str| source.parse::<Module>().unwrap();
+        assert_eq!(
+            without_offsets(&parse("let x = f (1, 2)")),
+            without_offsets(&parse("let   x =  f  ( 1,2 )"))
+        );
+        assert_ne!(
+            without_offsets(&parse("let x = 1")),
+            without_offsets(&parse("let x = 2"))
+        );
+        assert_ne!(
+            without_offsets(&parse("let x = 10")),
+            without_offsets(&parse("let x = 1"))
+        );
+    }
+
+    #[test]
+    fn formatting_is_idempotent_and_preserves_semantics() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let mut files: Vec<_> = fs::read_dir(root.join("ppl/src"))
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        for test in fs::read_dir(root.join("src/tests")).unwrap() {
+            let src = test.unwrap().path().join("src");
+            if let Ok(entries) = fs::read_dir(src) {
+                files.extend(entries.map(|entry| entry.unwrap().path()));
+            }
+        }
+        files.retain(|file| file.extension().is_some_and(|ext| ext == "ppl"));
+        assert!(!files.is_empty());
+
+        for file in files {
+            let source = normalize_indentation(&fs::read_to_string(&file).unwrap());
+            let Ok(original) = source.parse::<Module>() else {
+                assert!(format_code(&source).is_err(), "{}", file.display());
+                continue;
+            };
+
+            let formatted = format_code(&source).unwrap();
+            assert_eq!(
+                format_code(&formatted).unwrap(),
+                formatted,
+                "formatting of {} isn't idempotent",
+                file.display()
+            );
+
+            let reparsed = formatted.parse::<Module>().unwrap();
+            assert_eq!(
+                without_offsets(&reparsed),
+                without_offsets(&original),
+                "formatting changed meaning of {}",
+                file.display()
+            );
+            assert_eq!(
+                comments(&formatted),
+                comments(&source),
+                "{}",
+                file.display()
+            );
+        }
+    }
+}
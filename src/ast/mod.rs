@@ -12,3 +12,6 @@ pub use expressions::*;
 
 mod declarations;
 pub use declarations::*;
+
+mod format;
+pub use format::*;
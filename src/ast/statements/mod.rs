@@ -62,7 +62,8 @@ impl Ranged for Statement {
 impl StartsHere for Statement {
     /// Check that statement may start at current lexer position
     fn starts_here(context: &mut Context<impl Lexer>) -> bool {
-        Annotation::starts_here(context)
+        context.lexer.peek() == Some(Token::DocComment)
+            || Annotation::starts_here(context)
             || Declaration::starts_here(context)
             || Expression::starts_here(context)
             || Assignment::starts_here(context)
@@ -86,6 +87,14 @@ impl Parse for Statement {
             .into());
         }
 
+        let docs = context.parse_doc_comment();
+        if docs.is_some() && !Statement::starts_here(context) {
+            return Err(MissingStatement {
+                at: context.lexer.span().end.into(),
+            }
+            .into());
+        }
+
         let mut annotations = Vec::new();
         while Annotation::starts_here(context) {
             annotations.push(Annotation::parse(context)?);
@@ -117,6 +126,16 @@ impl Parse for Statement {
             }
         };
 
+        if docs.is_some() {
+            match res {
+                Statement::Declaration(Declaration::Function(ref mut decl)) => decl.docs = docs,
+                Statement::Declaration(Declaration::Type(ref mut decl)) => decl.docs = docs,
+                Statement::Declaration(Declaration::Trait(ref mut decl)) => decl.docs = docs,
+                // Documentation of other statements isn't kept
+                _ => {}
+            }
+        }
+
         if !annotations.is_empty() {
             match res {
                 Statement::Declaration(Declaration::Function(ref mut decl)) => {
@@ -0,0 +1,333 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use miette::miette;
+
+use crate::named::Named;
+
+use super::{Compiler, Module};
+
+/// Hash of module's source and of all modules it depends on
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct ModuleHash(u64);
+
+impl Display for ModuleHash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+/// 64-bit FNV-1a hasher.
+///
+/// Unlike [`std::hash::DefaultHasher`],
+/// its result doesn't depend on version of Rust or on platform,
+/// so it's safe to store on disk
+struct StableHasher(u64);
+
+impl StableHasher {
+    fn new() -> Self {
+        Self(0xcbf29ce484222325)
+    }
+
+    /// Hash bytes
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+    }
+
+    /// Hash bytes with their length, so that sequences of them are unambiguous
+    fn write_sized(&mut self, bytes: &[u8]) {
+        self.write(&(bytes.len() as u64).to_le_bytes());
+        self.write(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Identifier of compiler build.
+///
+/// It's a hash of compiler's executable,
+/// so that any rebuild of the compiler invalidates cache
+fn compiler_build_id() -> u64 {
+    static ID: OnceLock<u64> = OnceLock::new();
+    *ID.get_or_init(|| {
+        let mut hasher = StableHasher::new();
+        hasher.write_sized(env!("CARGO_PKG_VERSION").as_bytes());
+        let executable = std::env::current_exe().and_then(fs::read);
+        match executable {
+            Ok(bytes) => hasher.write_sized(&bytes),
+            // Cache is never reused, if compiler is unknown
+            Err(_) => hasher.write_sized(&std::process::id().to_le_bytes()),
+        }
+        hasher.finish()
+    })
+}
+
+/// State of Tarjan's search for strongly connected components of imports graph
+#[derive(Default)]
+struct ComponentsSearch {
+    /// Order, in which modules were visited
+    order: HashMap<Module, usize>,
+    /// Modules of components, that aren't finished yet
+    stack: Vec<Module>,
+    /// Modules, that are on the stack
+    on_stack: HashSet<Module>,
+}
+
+/// On-disk cache of compiled modules.
+///
+/// Entries are keyed by [`ModuleHash`],
+/// so module is reused only if neither it nor any of its imports changed.
+///
+/// Only generated code is cached:
+/// modules are still parsed and lowered to HIR on every build
+pub struct Cache {
+    /// Directory with cached entries
+    dir: PathBuf,
+    /// Already calculated hashes
+    hashes: HashMap<Module, ModuleHash>,
+}
+
+impl Cache {
+    /// Name of cache directory inside output directory
+    pub const DIR: &'static str = "cache";
+
+    /// Create cache, that stores entries at `dir`
+    pub fn at(dir: impl Into<PathBuf>) -> miette::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)
+            .map_err(|e| miette!("Can't create cache folder {}: {e}", dir.display()))?;
+        Ok(Self {
+            dir,
+            hashes: HashMap::new(),
+        })
+    }
+
+    /// Directory with cached entries
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Calculate hash of a module and all of its imports.
+    ///
+    /// Modules, that import each other, depend on sources of each other,
+    /// so they share the same hash
+    pub fn hash(&mut self, compiler: &Compiler, module: Module) -> ModuleHash {
+        if !self.hashes.contains_key(&module) {
+            self.visit(compiler, module, &mut ComponentsSearch::default());
+        }
+        self.hashes[&module]
+    }
+
+    /// Visit module in Tarjan's search and hash components, that are finished.
+    /// Returns order of the earliest visited module on the stack, reachable from this one
+    fn visit(
+        &mut self,
+        compiler: &Compiler,
+        module: Module,
+        search: &mut ComponentsSearch,
+    ) -> usize {
+        let order = search.order.len();
+        search.order.insert(module, order);
+        search.stack.push(module);
+        search.on_stack.insert(module);
+
+        let mut earliest = order;
+        for import in module.imports(compiler) {
+            if self.hashes.contains_key(&import) {
+                continue;
+            }
+            if !search.order.contains_key(&import) {
+                earliest = earliest.min(self.visit(compiler, import, search));
+            } else if search.on_stack.contains(&import) {
+                earliest = earliest.min(search.order[&import]);
+            }
+        }
+
+        if earliest == order {
+            let start = search
+                .stack
+                .iter()
+                .rposition(|m| *m == module)
+                .expect("Module should be on the stack");
+            let component = search.stack.split_off(start);
+            for m in &component {
+                search.on_stack.remove(m);
+            }
+            self.hash_component(compiler, component);
+        }
+        earliest
+    }
+
+    /// Hash modules, that import each other, together
+    fn hash_component(&mut self, compiler: &Compiler, mut component: Vec<Module>) {
+        // Hash mustn't depend on the module, where search started
+        component.sort_by_key(|m| m.data(compiler).source_file().path().to_path_buf());
+
+        let mut hasher = StableHasher::new();
+        hasher.write(&compiler_build_id().to_le_bytes());
+        for module in &component {
+            let source = module.data(compiler).source_file().contents();
+            hasher.write_sized(source.as_bytes());
+        }
+
+        let mut imports: Vec<u64> = component
+            .iter()
+            .flat_map(|module| module.imports(compiler))
+            .filter(|import| !component.contains(import))
+            .map(|import| self.hashes[&import].0)
+            .collect();
+        imports.sort();
+        imports.dedup();
+        for import in imports {
+            hasher.write(&import.to_le_bytes());
+        }
+
+        let hash = ModuleHash(hasher.finish());
+        for module in component {
+            self.hashes.insert(module, hash);
+        }
+    }
+
+    /// Path to the cached artifact of a module.
+    /// It may not exist yet.
+    ///
+    /// `tags` distinguish artifacts of the same module, built with different options
+    pub fn artifact(
+        &mut self,
+        compiler: &Compiler,
+        module: Module,
+        tags: &[&str],
+        extension: &str,
+    ) -> PathBuf {
+        let hash = self.hash(compiler, module);
+        let name = module.data(compiler).name().to_string();
+        let tags: String = tags.iter().map(|tag| format!("-{tag}")).collect();
+        self.dir.join(format!("{name}-{hash}{tags}.{extension}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use tempdir::TempDir;
+
+    use crate::compilation::Compiler;
+
+    use super::{Cache, ModuleHash};
+
+    /// Write a package, where `main` imports `greet`, that imports `names`,
+    /// and `main` also imports unrelated `answer`
+    fn write_package(root: &Path) {
+        let src = root.join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("names.ppl"), "fn world => \"World\"").unwrap();
+        fs::write(
+            src.join("greet.ppl"),
+            "use names.*\n\nfn greet => println \"Hello, \" + world",
+        )
+        .unwrap();
+        fs::write(src.join("answer.ppl"), "fn answer => 42").unwrap();
+        fs::write(
+            src.join("main.ppl"),
+            "use greet.*\nuse answer.*\n\ngreet\nprintln answer",
+        )
+        .unwrap();
+    }
+
+    /// Hashes of `main`, `greet`, `names` and `answer` modules
+    fn hashes(root: &Path, cache: &TempDir) -> [ModuleHash; 4] {
+        let mut compiler = Compiler::new().at(root);
+        let mut cache = Cache::at(cache.path()).unwrap();
+        ["main", "greet", "names", "answer"].map(|name| {
+            let module = compiler.compile(name).unwrap();
+            cache.hash(&compiler, module)
+        })
+    }
+
+    #[test]
+    fn hashes_are_stable() {
+        let package = TempDir::new("package").unwrap();
+        let cache = TempDir::new("cache").unwrap();
+        write_package(package.path());
+
+        assert_eq!(
+            hashes(package.path(), &cache),
+            hashes(package.path(), &cache)
+        );
+    }
+
+    #[test]
+    fn touching_module_invalidates_only_dependents() {
+        let package = TempDir::new("package").unwrap();
+        let cache = TempDir::new("cache").unwrap();
+        write_package(package.path());
+
+        let [main, greet, names, answer] = hashes(package.path(), &cache);
+
+        fs::write(
+            package.path().join("src/names.ppl"),
+            "fn world => \"Everyone\"",
+        )
+        .unwrap();
+
+        let [new_main, new_greet, new_names, new_answer] = hashes(package.path(), &cache);
+        assert_ne!(main, new_main);
+        assert_ne!(greet, new_greet);
+        assert_ne!(names, new_names);
+        assert_eq!(answer, new_answer);
+    }
+
+    #[test]
+    fn import_cycle_is_hashed_regardless_of_order() {
+        let package = TempDir::new("package").unwrap();
+        let cache = TempDir::new("cache").unwrap();
+        write_package(package.path());
+
+        let mut compiler = Compiler::new().at(package.path());
+        let main = compiler.compile("main").unwrap();
+        let greet = compiler.compile("greet").unwrap();
+        let names = compiler.compile("names").unwrap();
+        // Make `names` import `greet` back
+        compiler.imports.entry(names).or_default().insert(greet);
+
+        let mut from_main = Cache::at(cache.path()).unwrap();
+        let [main_first, greet_second, names_third] =
+            [main, greet, names].map(|module| from_main.hash(&compiler, module));
+        let mut from_names = Cache::at(cache.path()).unwrap();
+        let [names_first, greet_second_again, main_third] =
+            [names, greet, main].map(|module| from_names.hash(&compiler, module));
+
+        assert_eq!(main_first, main_third);
+        assert_eq!(greet_second, greet_second_again);
+        assert_eq!(names_third, names_first);
+        assert_eq!(greet_second, names_third);
+        assert_ne!(main_first, greet_second);
+    }
+
+    #[test]
+    fn cached_artifact_is_found() {
+        let package = TempDir::new("package").unwrap();
+        let cache_dir = TempDir::new("cache").unwrap();
+        write_package(package.path());
+
+        let mut compiler = Compiler::new().at(package.path());
+        let mut cache = Cache::at(cache_dir.path()).unwrap();
+        let answer = compiler.compile("answer").unwrap();
+
+        assert!(!cache.artifact(&compiler, answer, &[], "o").exists());
+        fs::write(cache.artifact(&compiler, answer, &[], "o"), "").unwrap();
+        assert!(cache.artifact(&compiler, answer, &[], "o").exists());
+        assert!(!cache.artifact(&compiler, answer, &["main"], "o").exists());
+    }
+}
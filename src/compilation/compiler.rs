@@ -1,9 +1,10 @@
 use std::{
+    collections::HashMap,
     env::current_dir,
     path::{Path, PathBuf},
 };
 
-use indexmap::IndexMap;
+use indexmap::{IndexMap, IndexSet};
 
 use crate::{
     ast,
@@ -37,6 +38,11 @@ impl Module {
     pub fn data<'c>(&self, compiler: &'c Compiler) -> &'c ModuleData {
         compiler.modules.get_index(self.index()).unwrap().1
     }
+
+    /// Modules, directly imported by this module
+    pub fn imports<'c>(&self, compiler: &'c Compiler) -> impl Iterator<Item = Module> + 'c {
+        compiler.imports.get(self).into_iter().flatten().cloned()
+    }
 }
 
 /// Index of a function in Compiler
@@ -79,6 +85,8 @@ pub struct Compiler {
     pub modules_stack: Vec<Module>,
     /// Cache of compiled modules
     pub modules: IndexMap<PathBuf, ModuleData>,
+    /// Modules, directly imported by each module
+    pub imports: HashMap<Module, IndexSet<Module>>,
     /// Functions from all modules
     pub functions: Vec<FunctionData>,
     /// Classes from all modules
@@ -116,6 +124,7 @@ impl Compiler {
             package_stack: Default::default(),
             modules_stack: Default::default(),
             modules: Default::default(),
+            imports: Default::default(),
             functions: Default::default(),
             classes: Default::default(),
             traits: Default::default(),
@@ -225,6 +234,46 @@ impl Compiler {
         Ok(module)
     }
 
+    /// Compile module from source file, that may be not saved on disk.
+    ///
+    /// Module is cached by name of the source file and recompiled on each call
+    pub fn compile_source(&mut self, source_file: SourceFile) -> miette::Result<Module> {
+        let ast = source_file
+            .contents()
+            .parse::<ast::Module>()
+            .map_err(|e| miette::Report::from(e).with_source_code(source_file.clone()))?;
+
+        let key = PathBuf::from(source_file.name());
+        let index = match self.modules.get_index_of(&key) {
+            Some(index) => index,
+            None => {
+                self.modules
+                    .insert_full(key, ModuleData::new(source_file.clone()))
+                    .0
+            }
+        };
+        let module = Module::with_index(index);
+        self.forget(module);
+        self.modules[index] = ModuleData::new(source_file.clone());
+
+        trace!(target: "steps", "Lowering to hir `{}`", source_file.name());
+        self.modules_stack.push(module);
+        let mut context = ModuleContext::new(ModuleData::new(source_file.clone()), self);
+        let hir = ast.to_hir(&mut context);
+        self.modules_stack.pop();
+
+        self.modules[index] =
+            hir.map_err(|e| miette::Report::from(e).with_source_code(source_file))?;
+        Ok(module)
+    }
+
+    /// Forget declarations and imports of module, so it can be compiled again
+    fn forget(&mut self, module: Module) {
+        self.imports.remove(&module);
+        self.functions.retain(|f| f.module != module);
+        self.traits.retain(|_, tr| tr.module != module);
+    }
+
     /// Locates package by name. Returns relative path (except for `ppl` package)
     fn locate_package(&mut self, package: &str) -> miette::Result<PathBuf> {
         if package == "ppl" {
@@ -290,3 +339,39 @@ impl Compiler {
         Ok(package)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use miette::NamedSource;
+    use tempdir::TempDir;
+
+    use crate::{named::Named, SourceFile};
+
+    use super::Compiler;
+
+    #[test]
+    fn recompiled_source_forgets_old_imports() {
+        let package = TempDir::new("package").unwrap();
+        let src = package.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("answer.ppl"), "fn answer => 42").unwrap();
+
+        let mut compiler = Compiler::new().at(package.path());
+        let source =
+            |code: &str| SourceFile::in_memory(NamedSource::new("main.ppl", code.to_string()));
+        let imports_answer = |compiler: &Compiler, main: super::Module| {
+            main.imports(compiler)
+                .any(|import| import.data(compiler).name() == "answer")
+        };
+
+        let main = compiler
+            .compile_source(source("use answer.*\n\nprintln answer"))
+            .unwrap();
+        assert!(imports_answer(&compiler, main));
+
+        let main = compiler.compile_source(source("println 42")).unwrap();
+        assert!(!imports_answer(&compiler, main));
+    }
+}
@@ -3,3 +3,6 @@ pub use compiler::*;
 
 mod package;
 pub use package::*;
+
+mod cache;
+pub use cache::*;
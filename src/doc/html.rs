@@ -0,0 +1,366 @@
+use std::collections::HashMap;
+
+use crate::{
+    compilation::{Compiler, Module, Package},
+    hir::{self, Basename, Class, Function, FunctionData, FunctionNamePart, Generic, Trait, Type},
+    named::Named,
+    semantics::{Implements, ModuleContext},
+    DataHolder,
+};
+
+use super::markdown::{self, escape};
+
+/// Styles, shared by all pages
+const STYLE: &str = "
+body { font-family: sans-serif; max-width: 60em; margin: 2em auto; line-height: 1.5; }
+code, pre { font-family: monospace; background: #f4f4f4; }
+pre { padding: 0.5em; }
+section { margin-bottom: 1.5em; }
+.signature { font-weight: bold; }
+";
+
+/// Page of generated documentation
+#[derive(Debug, Clone)]
+pub struct Page {
+    /// Name of the page file, like `index.html`
+    pub file_name: String,
+    /// HTML content of the page
+    pub html: String,
+}
+
+/// Declarations of a module, that are documented
+#[derive(Debug, Clone)]
+pub struct ModuleDocs {
+    /// Documented module
+    pub module: Module,
+    /// Name of the module
+    pub name: String,
+    /// Types, declared in module
+    pub types: Vec<Class>,
+    /// Traits, declared in module
+    pub traits: Vec<Trait>,
+    /// Functions, declared in module
+    pub functions: Vec<Function>,
+}
+
+impl ModuleDocs {
+    /// Collect declarations of compiled module
+    pub fn of(module: Module, compiler: &Compiler) -> Self {
+        let data = module.data(compiler);
+
+        let mut types = Vec::new();
+        let mut traits = Vec::new();
+        let mut functions = Vec::new();
+        for statement in &data.statements {
+            let hir::Statement::Declaration(declaration) = statement else {
+                continue;
+            };
+
+            match declaration {
+                hir::Declaration::Type(ty) => types.push(ty.clone()),
+                hir::Declaration::Trait(tr) => traits.push(tr.clone()),
+                hir::Declaration::Function(f) if !f.read().unwrap().is_test => {
+                    functions.push(f.clone())
+                }
+                _ => {}
+            }
+        }
+
+        Self {
+            module,
+            name: data.name().to_string(),
+            types,
+            traits,
+            functions,
+        }
+    }
+
+    /// Documentation comments of all declarations in this module
+    /// with names of documented declarations
+    pub fn docs(&self) -> Vec<(String, String)> {
+        let mut docs = Vec::new();
+        for ty in &self.types {
+            let ty = ty.read().unwrap();
+            docs.extend(ty.docs.clone().map(|d| (ty.name().to_string(), d)));
+        }
+        for tr in &self.traits {
+            let tr = tr.read().unwrap();
+            docs.extend(tr.docs.clone().map(|d| (tr.name().to_string(), d)));
+            for f in tr.functions.values() {
+                let f = f.read().unwrap();
+                docs.extend(f.docs.clone().map(|d| (f.name().to_string(), d)));
+            }
+        }
+        for f in &self.functions {
+            let f = f.read().unwrap();
+            docs.extend(f.docs.clone().map(|d| (f.name().to_string(), d)));
+        }
+        docs
+    }
+}
+
+/// Generator of static HTML documentation for a package
+pub struct DocGenerator {
+    /// Name of documented package
+    package: String,
+    /// Documented modules
+    modules: Vec<ModuleDocs>,
+    /// Links to declarations of types and traits by their names
+    links: HashMap<String, String>,
+    /// Types, implementing trait, by name of trait
+    implementors: HashMap<String, Vec<Class>>,
+}
+
+impl DocGenerator {
+    /// Collect documentation of already compiled package
+    pub fn new(package: Package, compiler: &mut Compiler) -> Self {
+        let data = package.data(compiler);
+        let name = data.name.clone();
+        let modules: Vec<_> = data
+            .modules
+            .clone()
+            .into_iter()
+            .map(|m| ModuleDocs::of(m, compiler))
+            .collect();
+
+        let mut links = HashMap::new();
+        for m in &modules {
+            for ty in &m.types {
+                let name = ty.basename().to_string();
+                links.insert(name.clone(), format!("{}.html#type.{name}", m.name));
+            }
+            for tr in &m.traits {
+                let name = tr.name().to_string();
+                links.insert(name.clone(), format!("{}.html#trait.{name}", m.name));
+            }
+        }
+
+        let traits: Vec<_> = modules.iter().flat_map(|m| m.traits.clone()).collect();
+        let mut implementors: HashMap<String, Vec<Class>> = HashMap::new();
+        for m in &modules {
+            let classes: Vec<_> = m.types.iter().filter(|ty| !ty.is_generic()).collect();
+            if classes.is_empty() || traits.is_empty() {
+                continue;
+            }
+
+            let mut context = ModuleContext::new(m.module.data(compiler).clone(), compiler);
+            for tr in &traits {
+                for class in &classes {
+                    if class.implements(tr.clone()).within(&mut context).is_ok() {
+                        implementors
+                            .entry(tr.name().to_string())
+                            .or_default()
+                            .push((*class).clone());
+                    }
+                }
+            }
+        }
+
+        Self {
+            package: name,
+            modules,
+            links,
+            implementors,
+        }
+    }
+
+    /// Documented modules
+    pub fn modules(&self) -> &[ModuleDocs] {
+        &self.modules
+    }
+
+    /// Generate all pages of documentation
+    pub fn pages(&self) -> Vec<Page> {
+        let mut pages = vec![Page {
+            file_name: "index.html".to_string(),
+            html: self.index(),
+        }];
+        pages.extend(self.modules.iter().map(|m| Page {
+            file_name: format!("{}.html", m.name),
+            html: self.module_page(m),
+        }));
+        pages
+    }
+
+    /// Wrap body of page into HTML document
+    fn page(&self, title: &str, body: &str) -> String {
+        format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n{body}</body>\n</html>\n",
+            title = escape(title)
+        )
+    }
+
+    /// Index page with all modules of the package
+    fn index(&self) -> String {
+        let mut body = format!("<h1>Package <code>{}</code></h1>\n", escape(&self.package));
+        body.push_str("<h2>Modules</h2>\n<ul>\n");
+        for m in &self.modules {
+            body.push_str(&format!(
+                "<li><a href=\"{name}.html\">{name}</a></li>\n",
+                name = escape(&m.name)
+            ));
+        }
+        body.push_str("</ul>\n");
+        self.page(&self.package, &body)
+    }
+
+    /// Page with all declarations of a module
+    fn module_page(&self, m: &ModuleDocs) -> String {
+        let mut body = format!(
+            "<nav><a href=\"index.html\">{}</a></nav>\n<h1>Module <code>{}</code></h1>\n",
+            escape(&self.package),
+            escape(&m.name)
+        );
+
+        if !m.types.is_empty() {
+            body.push_str("<h2>Types</h2>\n");
+            m.types
+                .iter()
+                .for_each(|ty| body.push_str(&self.class_section(ty)));
+        }
+
+        if !m.traits.is_empty() {
+            body.push_str("<h2>Traits</h2>\n");
+            m.traits
+                .iter()
+                .for_each(|tr| body.push_str(&self.trait_section(tr)));
+        }
+
+        if !m.functions.is_empty() {
+            body.push_str("<h2>Functions</h2>\n");
+            m.functions
+                .iter()
+                .for_each(|f| body.push_str(&self.function_section(&f.read().unwrap())));
+        }
+
+        self.page(&format!("{} - {}", m.name, self.package), &body)
+    }
+
+    /// Documentation of a type with its members
+    fn class_section(&self, class: &Class) -> String {
+        let class = class.read().unwrap();
+        let mut html = format!(
+            "<section id=\"type.{basename}\">\n<h3><code class=\"signature\">type {name}</code></h3>\n",
+            basename = escape(&class.basename()),
+            name = escape(&class.name())
+        );
+        html.push_str(&self.docs(&class.docs));
+
+        if !class.members.is_empty() {
+            html.push_str("<h4>Members</h4>\n<ul>\n");
+            for member in &class.members {
+                html.push_str(&format!(
+                    "<li><code>{}: {}</code></li>\n",
+                    escape(&member.name()),
+                    self.type_link(&member.read().unwrap().ty)
+                ));
+            }
+            html.push_str("</ul>\n");
+        }
+
+        html.push_str("</section>\n");
+        html
+    }
+
+    /// Documentation of a trait with its functions and implementors
+    fn trait_section(&self, tr: &Trait) -> String {
+        let tr = tr.read().unwrap();
+        let mut html = format!(
+            "<section id=\"trait.{name}\">\n<h3><code class=\"signature\">trait {name}</code></h3>\n",
+            name = escape(&tr.name())
+        );
+        html.push_str(&self.docs(&tr.docs));
+
+        if !tr.supertraits.is_empty() {
+            let supertraits: Vec<_> = tr
+                .supertraits
+                .iter()
+                .map(|s| self.type_link(&s.clone().into()))
+                .collect();
+            html.push_str(&format!("<p>Requires {}</p>\n", supertraits.join(", ")));
+        }
+
+        if !tr.functions.is_empty() {
+            html.push_str("<h4>Functions</h4>\n");
+            tr.functions
+                .values()
+                .for_each(|f| html.push_str(&self.function_section(&f.read().unwrap())));
+        }
+
+        if let Some(implementors) = self.implementors.get(tr.name().as_ref()) {
+            html.push_str("<h4>Implementors</h4>\n<ul>\n");
+            for class in implementors {
+                html.push_str(&format!(
+                    "<li><code>{}</code></li>\n",
+                    self.type_link(&class.clone().into())
+                ));
+            }
+            html.push_str("</ul>\n");
+        }
+
+        html.push_str("</section>\n");
+        html
+    }
+
+    /// Documentation of a function in its mixfix form
+    fn function_section(&self, f: &FunctionData) -> String {
+        let mut html = format!(
+            "<section id=\"fn.{id}\">\n<h3><code class=\"signature\">{signature}</code></h3>\n",
+            id = escape(&f.name().replace(' ', "_")),
+            signature = self.signature(f)
+        );
+        html.push_str(&self.docs(&f.docs));
+        html.push_str("</section>\n");
+        html
+    }
+
+    /// Signature of function with links to types
+    fn signature(&self, f: &FunctionData) -> String {
+        let generics = if f.generic_types.is_empty() {
+            String::new()
+        } else {
+            let generics: Vec<_> = f.generic_types.iter().map(|ty| format!("{ty:+}")).collect();
+            escape(&format!("<{}>", generics.join(", ")))
+        };
+
+        let name_parts: Vec<_> = f
+            .name_parts()
+            .iter()
+            .map(|part| match part {
+                FunctionNamePart::Text(text) => escape(text),
+                FunctionNamePart::Parameter(p) => {
+                    let ty = self.type_link(&p.read().unwrap().ty.referenced_type);
+                    if p.name().is_empty() {
+                        format!("&lt;:{ty}&gt;")
+                    } else {
+                        format!("&lt;{}: {ty}&gt;", escape(&p.name()))
+                    }
+                }
+            })
+            .collect();
+
+        format!(
+            "fn{generics} {} -&gt; {}",
+            name_parts.join(" "),
+            self.type_link(&f.return_type)
+        )
+    }
+
+    /// Name of type, linked to its documentation, if it's declared in this package
+    fn type_link(&self, ty: &Type) -> String {
+        let name = escape(&ty.name());
+        if !matches!(ty, Type::Class(_) | Type::Trait(_)) {
+            return name;
+        }
+
+        match self.links.get(ty.basename().as_ref()) {
+            Some(link) => format!("<a href=\"{link}\">{name}</a>"),
+            None => name,
+        }
+    }
+
+    /// Rendered documentation comment
+    fn docs(&self, docs: &Option<String>) -> String {
+        docs.as_deref().map(markdown::to_html).unwrap_or_default()
+    }
+}
@@ -0,0 +1,169 @@
+/// Block of a documentation comment
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Block {
+    /// Paragraph of text
+    Paragraph(String),
+    /// Fenced code block
+    Code {
+        /// Info string after opening fence, like `ppl` or `text`
+        lang: String,
+        /// Code inside the fences
+        code: String,
+    },
+}
+
+impl Block {
+    /// Is this a code block, that should be compiled and run as doctest?
+    ///
+    /// Blocks without language or with `ppl` language are doctests
+    pub fn is_doctest(&self) -> bool {
+        matches!(self, Block::Code { lang, .. } if lang.is_empty() || lang == "ppl")
+    }
+}
+
+/// Split documentation comment into paragraphs and code blocks
+pub fn blocks(docs: &str) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut code: Option<(String, Vec<&str>)> = None;
+
+    let end_paragraph = |paragraph: &mut Vec<&str>, blocks: &mut Vec<Block>| {
+        if !paragraph.is_empty() {
+            blocks.push(Block::Paragraph(paragraph.join("\n")));
+            paragraph.clear();
+        }
+    };
+
+    for line in docs.lines() {
+        let fence = line.trim_start().strip_prefix("```");
+
+        if let Some((lang, lines)) = &mut code {
+            if fence.is_none() {
+                lines.push(line);
+                continue;
+            }
+
+            blocks.push(Block::Code {
+                lang: std::mem::take(lang),
+                code: lines.join("\n"),
+            });
+            code = None;
+        } else if let Some(lang) = fence {
+            end_paragraph(&mut paragraph, &mut blocks);
+            code = Some((lang.trim().to_string(), Vec::new()));
+        } else if line.trim().is_empty() {
+            end_paragraph(&mut paragraph, &mut blocks);
+        } else {
+            paragraph.push(line.trim());
+        }
+    }
+
+    end_paragraph(&mut paragraph, &mut blocks);
+    if let Some((lang, lines)) = code {
+        blocks.push(Block::Code {
+            lang,
+            code: lines.join("\n"),
+        });
+    }
+
+    blocks
+}
+
+/// Get code of doctests from documentation comment
+pub fn doctests(docs: &str) -> Vec<String> {
+    blocks(docs)
+        .into_iter()
+        .filter(Block::is_doctest)
+        .map(|block| match block {
+            Block::Code { code, .. } => code,
+            Block::Paragraph(_) => unreachable!("doctest is always a code block"),
+        })
+        .collect()
+}
+
+/// Escape special HTML characters
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '&' => escaped.push_str("&amp;"),
+            '"' => escaped.push_str("&quot;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Render text with `inline code` to HTML
+fn inline(text: &str) -> String {
+    text.split('`')
+        .enumerate()
+        .map(|(i, part)| {
+            if i % 2 == 1 {
+                format!("<code>{}</code>", escape(part))
+            } else {
+                escape(part)
+            }
+        })
+        .collect()
+}
+
+/// Render documentation comment to HTML
+pub fn to_html(docs: &str) -> String {
+    blocks(docs)
+        .into_iter()
+        .map(|block| match block {
+            Block::Paragraph(text) => format!("<p>{}</p>\n", inline(&text)),
+            Block::Code { code, .. } => format!("<pre><code>{}</code></pre>\n", escape(&code)),
+        })
+        .collect()
+}
+
+/// First paragraph of documentation comment, rendered to HTML
+pub fn summary(docs: &str) -> String {
+    blocks(docs)
+        .into_iter()
+        .find_map(|block| match block {
+            Block::Paragraph(text) => Some(inline(&text)),
+            Block::Code { .. } => None,
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn split_into_blocks() {
+        let docs = "Sum of\n`a` and `b`\n\n```\nprintln 1 + 2\n```\n```text\n3\n```";
+        assert_eq!(
+            blocks(docs),
+            vec![
+                Block::Paragraph("Sum of\n`a` and `b`".to_string()),
+                Block::Code {
+                    lang: "".to_string(),
+                    code: "println 1 + 2".to_string()
+                },
+                Block::Code {
+                    lang: "text".to_string(),
+                    code: "3".to_string()
+                },
+            ]
+        );
+        assert_eq!(doctests(docs), vec!["println 1 + 2".to_string()]);
+    }
+
+    #[test]
+    fn render_html() {
+        assert_eq!(
+            to_html("Returns `a < b`\n\n```\nprintln <:Integer>\n```"),
+            "<p>Returns <code>a &lt; b</code></p>\n<pre><code>println &lt;:Integer&gt;</code></pre>\n"
+        );
+        assert_eq!(summary("First\nline\n\nSecond"), "First\nline");
+    }
+}
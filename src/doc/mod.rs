@@ -0,0 +1,4 @@
+pub mod markdown;
+
+mod html;
+pub use html::*;
@@ -1,10 +1,15 @@
-use self::commands::{Build, New, Run};
+use self::commands::{
+    compile::OptimizationLevel, Build, Check, Doc, Explain, Fmt, Lsp, New, Run, Test,
+};
 use clap::{Parser, Subcommand};
 use derive_more::From;
 
 /// PPL's package manager
 #[derive(Parser, Debug)]
 pub struct Args {
+    /// Optimization level of code, executed in REPL
+    #[arg(short = 'O', value_name = "level", default_value = "0")]
+    pub opt_level: OptimizationLevel,
     /// Subcommand to execute
     #[command(subcommand)]
     pub command: Option<Command>,
@@ -17,16 +22,28 @@ pub enum Command {
     New(New),
     /// Build package
     Build(Build),
+    /// Check package for errors without generating code
+    Check(Check),
     /// Build and run package
     Run(Run),
+    /// Build and run tests of package
+    Test(Test),
+    /// Format source code of package
+    Fmt(Fmt),
+    /// Generate documentation of package
+    Doc(Doc),
+    /// Run language server over stdio
+    Lsp(Lsp),
+    /// Explain error code
+    Explain(Explain),
 }
 
 pub mod commands {
-    use std::path::PathBuf;
+    use std::{num::NonZeroUsize, path::PathBuf};
 
     use clap::Parser;
 
-    use self::compile::OutputType;
+    use self::compile::{MessageFormat, OptimizationLevel, OutputType};
 
     /// Command to create a new package
     #[derive(Parser, Debug)]
@@ -45,6 +62,44 @@ pub mod commands {
         /// Output type of compilation
         #[arg(long = "emit", value_name = "output type")]
         pub output_type: Option<OutputType>,
+        /// Recompile all modules, ignoring cached ones
+        #[arg(long)]
+        pub no_cache: bool,
+        /// Number of modules to generate code for in parallel.
+        /// Defaults to the number of available CPUs
+        #[arg(short, long, value_name = "N")]
+        pub jobs: Option<NonZeroUsize>,
+        /// Instrument generated code with AddressSanitizer
+        #[arg(long)]
+        pub sanitize: bool,
+        /// Generate debug information
+        #[arg(long)]
+        pub debug_info: bool,
+        /// Directory with runtime library
+        #[arg(long, value_name = "dir")]
+        pub runtime_path: Option<PathBuf>,
+        /// Build runtime library from source, instead of using a prebuilt one
+        #[arg(long, conflicts_with = "runtime_path")]
+        pub build_runtime: bool,
+        /// Build with optimizations. Same as `-O3`, unless level is specified explicitly
+        #[arg(long)]
+        pub release: bool,
+        /// Optimization level
+        #[arg(short = 'O', value_name = "level")]
+        pub opt_level: Option<OptimizationLevel>,
+        /// Target triple to compile for, like `aarch64-unknown-linux-gnu`.
+        /// Defaults to the host
+        #[arg(long, value_name = "triple")]
+        pub target: Option<String>,
+        /// Sysroot of the target, passed to the linker
+        #[arg(long, value_name = "dir")]
+        pub sysroot: Option<PathBuf>,
+        /// Format of reported diagnostics
+        #[arg(long, value_name = "format", default_value = "human")]
+        pub message_format: MessageFormat,
+        /// Build test harness for functions marked with `@test`, instead of the package itself
+        #[arg(skip)]
+        pub tests: bool,
     }
 
     impl Default for Build {
@@ -52,13 +107,110 @@ pub mod commands {
             Self {
                 output_dir: PathBuf::from("target"),
                 output_type: None,
+                no_cache: false,
+                jobs: None,
+                sanitize: false,
+                debug_info: false,
+                runtime_path: None,
+                build_runtime: false,
+                release: false,
+                opt_level: None,
+                target: None,
+                sysroot: None,
+                message_format: MessageFormat::Human,
+                tests: false,
             }
         }
     }
 
+    impl Build {
+        /// Optimization level to build with
+        pub fn optimization_level(&self) -> OptimizationLevel {
+            self.opt_level.unwrap_or(if self.release {
+                OptimizationLevel::Aggressive
+            } else {
+                OptimizationLevel::None
+            })
+        }
+    }
+
+    /// Command to check a package for errors without generating code
+    #[derive(Parser, Debug)]
+    pub struct Check {
+        /// Format of reported diagnostics
+        #[arg(long, value_name = "format", default_value = "human")]
+        pub message_format: MessageFormat,
+    }
+
     /// Command to build and run a package
     #[derive(Parser, Debug)]
-    pub struct Run {}
+    pub struct Run {
+        /// Directory where compiler output will be placed.
+        #[arg(long, value_name = "dir", default_value = "target")]
+        pub output_dir: PathBuf,
+        /// Build with optimizations. Same as `-O3`, unless level is specified explicitly
+        #[arg(long)]
+        pub release: bool,
+        /// Optimization level
+        #[arg(short = 'O', value_name = "level")]
+        pub opt_level: Option<OptimizationLevel>,
+        /// Build runtime library from source, instead of using a prebuilt one
+        #[arg(long)]
+        pub build_runtime: bool,
+        /// Format of reported diagnostics
+        #[arg(long, value_name = "format", default_value = "human")]
+        pub message_format: MessageFormat,
+        /// Arguments for the program, given after `--`
+        #[arg(last = true, value_name = "args")]
+        pub args: Vec<String>,
+    }
+
+    /// Command to build and run tests of a package
+    #[derive(Parser, Debug)]
+    pub struct Test {
+        /// Directory where compiler output will be placed.
+        #[arg(long, value_name = "dir", default_value = "target")]
+        pub output_dir: PathBuf,
+        /// Run only tests, which names contain any of these strings
+        #[arg(value_name = "filter")]
+        pub filters: Vec<String>,
+    }
+
+    /// Command to format source code
+    #[derive(Parser, Debug)]
+    pub struct Fmt {
+        /// Don't change files, but fail if any of them isn't formatted
+        #[arg(long)]
+        pub check: bool,
+        /// Files to format.
+        /// Defaults to all `.ppl` files in `src` directory of the package
+        #[arg(value_name = "file")]
+        pub files: Vec<PathBuf>,
+    }
+
+    /// Command to generate documentation of a package
+    #[derive(Parser, Debug)]
+    pub struct Doc {
+        /// Directory where documentation will be placed, under `doc` subdirectory
+        #[arg(long, value_name = "dir", default_value = "target")]
+        pub output_dir: PathBuf,
+        /// Compile and run code examples from documentation instead
+        #[arg(long)]
+        pub test: bool,
+    }
+
+    /// Command to run language server
+    #[derive(Parser, Debug)]
+    pub struct Lsp {}
+
+    /// Command to explain error codes
+    #[derive(Parser, Debug)]
+    pub struct Explain {
+        /// Error code to explain, like `semantics::no_function`.
+        /// Lists all explained codes, if omitted
+        #[arg(value_name = "code")]
+        pub code: Option<String>,
+    }
 
     pub mod compile {
         use std::str::FromStr;
@@ -78,62 +230,154 @@ pub mod commands {
             DynamicLibrary,
         }
 
+        /// Format of reported diagnostics
+        #[derive(Debug, Default, PartialEq, Eq, Clone, Copy, ValueEnum)]
+        pub enum MessageFormat {
+            /// Rendered for humans
+            #[default]
+            Human,
+            /// One JSON object per diagnostic on a line
+            Json,
+        }
+
+        /// Operating system of compilation target
+        #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+        pub enum TargetOs {
+            /// Linux and other unix-like systems
+            Unix,
+            /// macOS and other Apple systems
+            MacOS,
+            /// Windows
+            Windows,
+            /// WebAssembly without operating system
+            Wasm,
+        }
+
+        impl TargetOs {
+            /// Operating system of the host
+            pub fn host() -> Self {
+                if cfg!(target_os = "windows") {
+                    Self::Windows
+                } else if cfg!(target_os = "macos") {
+                    Self::MacOS
+                } else {
+                    Self::Unix
+                }
+            }
+
+            /// Operating system for target triple, like `aarch64-unknown-linux-gnu`
+            pub fn from_triple(triple: &str) -> Self {
+                if triple.starts_with("wasm") {
+                    Self::Wasm
+                } else if triple.contains("windows") {
+                    Self::Windows
+                } else if triple.contains("apple") || triple.contains("darwin") {
+                    Self::MacOS
+                } else {
+                    Self::Unix
+                }
+            }
+        }
+
         impl OutputType {
-            /// Extension associated with this output type
+            /// Extension associated with this output type on the host
             pub fn extension(&self) -> &'static str {
+                self.extension_for(TargetOs::host())
+            }
+
+            /// Extension associated with this output type on specified operating system
+            pub fn extension_for(&self, os: TargetOs) -> &'static str {
                 match self {
                     Self::HIR => "hir",
                     Self::IR => "ll",
                     Self::Bitcode => "bc",
                     Self::Object => "o",
                     Self::Assembler => "s",
-                    Self::Executable => {
-                        if cfg!(target_os = "windows") {
-                            "exe"
-                        } else {
-                            "out"
-                        }
-                    }
-                    Self::StaticLibrary => {
-                        if cfg!(target_os = "windows") {
-                            "lib"
-                        } else {
-                            "a"
-                        }
-                    }
-                    Self::DynamicLibrary => {
-                        if cfg!(target_os = "windows") {
-                            "dll"
-                        } else if cfg!(target_os = "macos") {
-                            "dylib"
-                        } else {
-                            "so"
-                        }
-                    }
+                    Self::Executable => match os {
+                        TargetOs::Windows => "exe",
+                        TargetOs::Wasm => "wasm",
+                        TargetOs::Unix | TargetOs::MacOS => "out",
+                    },
+                    Self::StaticLibrary => match os {
+                        TargetOs::Windows => "lib",
+                        _ => "a",
+                    },
+                    Self::DynamicLibrary => match os {
+                        TargetOs::Windows => "dll",
+                        TargetOs::MacOS => "dylib",
+                        TargetOs::Wasm => "wasm",
+                        TargetOs::Unix => "so",
+                    },
                 }
             }
 
-            /// File prefix associated with this output file
+            /// File prefix associated with this output file on the host
             ///
             /// `Some("lib")` for [`DynamicLibrary`](OutputType::DynamicLibrary) and [`StaticLibrary`](OutputType::StaticLibrary);
             /// `None`, otherwise
             pub fn file_prefix(&self) -> Option<&'static str> {
+                self.file_prefix_for(TargetOs::host())
+            }
+
+            /// File prefix associated with this output file on specified operating system
+            ///
+            /// `Some("lib")` for [`DynamicLibrary`](OutputType::DynamicLibrary) and [`StaticLibrary`](OutputType::StaticLibrary),
+            /// unless it's Windows;
+            /// `None`, otherwise
+            pub fn file_prefix_for(&self, os: TargetOs) -> Option<&'static str> {
                 match self {
-                    Self::StaticLibrary | Self::DynamicLibrary => Some("lib"),
+                    Self::StaticLibrary | Self::DynamicLibrary if os != TargetOs::Windows => {
+                        Some("lib")
+                    }
                     _ => None,
                 }
             }
 
-            /// Get name of output file with correct prefix and extension
+            /// Get name of output file with correct prefix and extension for the host
             pub fn named(&self, name: &str) -> String {
+                self.named_for(name, TargetOs::host())
+            }
+
+            /// Get name of output file with correct prefix and extension for specified operating system
+            pub fn named_for(&self, name: &str, os: TargetOs) -> String {
                 format!(
                     "{prefix}{name}.{ext}",
-                    prefix = self.file_prefix().unwrap_or(""),
-                    ext = self.extension()
+                    prefix = self.file_prefix_for(os).unwrap_or(""),
+                    ext = self.extension_for(os)
                 )
             }
         }
 
+        /// Optimization level of generated code
+        #[derive(Debug, Default, PartialEq, Eq, Clone, Copy, ValueEnum)]
+        pub enum OptimizationLevel {
+            /// No optimizations
+            #[default]
+            #[value(name = "0")]
+            None,
+            /// Basic optimizations
+            #[value(name = "1")]
+            Less,
+            /// Most optimizations
+            #[value(name = "2")]
+            Default,
+            /// All optimizations
+            #[value(name = "3")]
+            Aggressive,
+        }
+
+        impl OptimizationLevel {
+            /// Numeric value of the level
+            pub fn as_number(&self) -> u8 {
+                match self {
+                    Self::None => 0,
+                    Self::Less => 1,
+                    Self::Default => 2,
+                    Self::Aggressive => 3,
+                }
+            }
+        }
+
         impl FromStr for OutputType {
             type Err = ();
 
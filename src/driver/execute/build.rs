@@ -1,18 +1,28 @@
 use std::{
     fs,
+    num::NonZeroUsize,
     path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
 };
 
-use cmd_lib::{run_cmd, run_fun};
+use cmd_lib::run_cmd;
+use inkwell::targets::{FileType, TargetMachine};
 use log::{debug, trace};
 use miette::{bail, miette};
 use tempdir::TempDir;
 
 use crate::{
-    compilation::{Compiler, Package},
-    driver::commands::{compile::OutputType, Build},
-    ir::HIRModuleLowering,
+    compilation::{Cache, Compiler, Module, Package},
+    driver::commands::{
+        compile::{OptimizationLevel, OutputType, TargetOs},
+        Build,
+    },
+    ir::{
+        optimize, sanitize_address, set_target, target_machine, test_harness, HIRModuleLowering,
+        TestCase,
+    },
     named::Named,
+    syntax::Ranged,
 };
 
 use super::Execute;
@@ -21,6 +31,14 @@ impl Execute for Build {
     type Output = miette::Result<PathBuf>;
 
     fn execute(&self) -> Self::Output {
+        self.message_format.report(self.build())
+    }
+}
+
+impl Build {
+    /// Build package in current directory.
+    /// Returns path to the built artifact
+    fn build(&self) -> miette::Result<PathBuf> {
         let cwd = std::env::current_dir().map_err(|e| miette!("{e}"))?;
         let package = cwd.file_name().unwrap().to_str().unwrap();
         let output_dir = self.output_dir.clone();
@@ -54,8 +72,444 @@ impl Execute for Build {
         )
         .map_err(|e| miette!("{e}"))?;
 
-        package.emit(compiler, output_dir, output_type, dependencies_dir)
+        let mut cache = if self.no_cache {
+            None
+        } else {
+            let target = self.target.as_deref().unwrap_or("host");
+            Some(Cache::at(output_dir.join(Cache::DIR).join(target))?)
+        };
+
+        let options = EmitOptions {
+            jobs: self.jobs.unwrap_or_else(|| {
+                std::thread::available_parallelism().unwrap_or(NonZeroUsize::MIN)
+            }),
+            debug_info: self.debug_info,
+            sanitize: self.sanitize,
+            optimization: self.optimization_level(),
+            runtime: if self.build_runtime {
+                self.build_runtime(&output_dir)?
+            } else {
+                self.locate_runtime()?
+            },
+            target: self.target.clone(),
+            sysroot: self.sysroot.clone(),
+            tests: self.tests,
+        };
+
+        package.emit(
+            compiler,
+            output_dir,
+            output_type,
+            dependencies_dir,
+            cache.as_mut(),
+            &options,
+        )
+    }
+
+    /// Name of runtime library to link with.
+    /// It's static for other targets and dynamic for the host
+    fn runtime_library(&self) -> String {
+        match &self.target {
+            Some(target) => {
+                OutputType::StaticLibrary.named_for("runtime", TargetOs::from_triple(target))
+            }
+            None => OutputType::DynamicLibrary.named("runtime"),
+        }
+    }
+
+    /// Locate directory with prebuilt runtime library
+    ///
+    /// # Search order
+    /// 1. `--runtime-path`
+    /// 2. `PPL_RUNTIME_PATH` environment variable
+    /// 3. `{exe_dir}/../lib`, `{exe_dir}` and `{exe_dir}/deps`, where `exe_dir` is directory of `ppl` executable
+    ///
+    /// Runtimes for other targets are looked up in subdirectories,
+    /// named after target triple, like `{exe_dir}/../lib/aarch64-unknown-linux-gnu`.
+    /// `--runtime-path` is used as is
+    fn locate_runtime(&self) -> miette::Result<PathBuf> {
+        let library = self.runtime_library();
+
+        if let Some(path) = &self.runtime_path {
+            if !path.join(&library).exists() {
+                bail!("No {library} found at {}", path.display());
+            }
+            return Ok(path.clone());
+        }
+
+        let mut variants = vec![];
+        if let Some(path) = std::env::var_os("PPL_RUNTIME_PATH") {
+            variants.push(PathBuf::from(path));
+        }
+        if let Some(exe_dir) = std::env::current_exe()
+            .ok()
+            .and_then(|exe| exe.parent().map(Path::to_path_buf))
+        {
+            variants.push(exe_dir.join("../lib"));
+            if self.target.is_none() {
+                variants.push(exe_dir.join("deps"));
+                variants.push(exe_dir);
+            }
+        }
+        if let Some(target) = &self.target {
+            variants = variants.iter().map(|dir| dir.join(target)).collect();
+        }
+
+        variants
+            .iter()
+            .find(|dir| dir.join(&library).exists())
+            .cloned()
+            .ok_or_else(|| {
+                miette!(
+                    "Runtime library {library} not found. Tried {:#?}. \
+                     Use --runtime-path or PPL_RUNTIME_PATH to specify its location \
+                     or --build-runtime to build it from source",
+                    variants
+                )
+            })
+    }
+
+    /// Build runtime library and its bitcode from source inside `output_dir`.
+    /// Optimized builds use runtime, built with `release` profile.
+    /// Returns directory with the library
+    fn build_runtime(&self, output_dir: &Path) -> miette::Result<PathBuf> {
+        let manifest = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/runtime/Cargo.toml");
+        let target_dir = output_dir
+            .canonicalize()
+            .map_err(|e| miette!("Can't canonicalize output folder: {e}"))?
+            .join("runtime");
+        let (profile, mut flags) = match self.optimization_level() {
+            OptimizationLevel::None => ("debug", vec![]),
+            _ => ("release", vec!["--release".to_string()]),
+        };
+        let mut library_dir = target_dir.clone();
+        if let Some(target) = &self.target {
+            flags.extend(["--target".to_string(), target.clone()]);
+            library_dir.push(target);
+        }
+        library_dir.push(profile);
+
+        let target = self.target.as_deref().unwrap_or("host");
+        trace!(target: "steps", "building {profile} runtime for {target}");
+        // Single codegen unit gives single bitcode file
+        let rustc_flags = ["--emit=llvm-bc,link", "-C", "codegen-units=1"];
+        run_cmd!(
+            cargo rustc --lib --manifest-path $manifest --target-dir $target_dir $[flags] -- $[rustc_flags]
+        )
+        .map_err(|e| miette!("Can't build runtime for `{target}`: {e}"))?;
+
+        // Bitcode is named like `runtime-{hash}.bc`
+        let deps = library_dir.join("deps");
+        let bitcode = fs::read_dir(&deps)
+            .map_err(|e| miette!("Can't read {}: {e}", deps.display()))?
+            .filter_map(Result::ok)
+            .filter(|entry| {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                name.starts_with("runtime-") && name.ends_with(".bc")
+            })
+            .max_by_key(|entry| entry.metadata().and_then(|m| m.modified()).ok())
+            .ok_or_else(|| miette!("No runtime bitcode found at {}", deps.display()))?
+            .path();
+        let runtime_bitcode = library_dir.join("runtime.bc");
+        fs::copy(&bitcode, &runtime_bitcode).map_err(|e| {
+            miette!(
+                "Can't copy {} to {}: {e}",
+                bitcode.display(),
+                runtime_bitcode.display()
+            )
+        })?;
+
+        Ok(library_dir)
+    }
+}
+
+/// Options for emitting packages
+#[derive(Debug, Clone)]
+struct EmitOptions {
+    /// Number of modules to generate code for in parallel
+    jobs: NonZeroUsize,
+    /// Keep debug information in generated code
+    debug_info: bool,
+    /// Instrument generated machine code with AddressSanitizer
+    sanitize: bool,
+    /// Optimization level of generated code
+    optimization: OptimizationLevel,
+    /// Directory with runtime library
+    runtime: PathBuf,
+    /// Target triple. Host, if not specified
+    target: Option<String>,
+    /// Sysroot of the target for the linker
+    sysroot: Option<PathBuf>,
+    /// Build test harness, that runs `@test` functions, instead of package itself
+    tests: bool,
+}
+
+impl EmitOptions {
+    /// Tags, that distinguish cached artifacts of modules, built with these options
+    fn tags(&self, with_main: bool) -> Vec<&'static str> {
+        let optimization = match self.optimization {
+            OptimizationLevel::None => None,
+            OptimizationLevel::Less => Some("O1"),
+            OptimizationLevel::Default => Some("O2"),
+            OptimizationLevel::Aggressive => Some("O3"),
+        };
+        [
+            with_main.then_some("main"),
+            self.debug_info.then_some("g"),
+            self.sanitize.then_some("asan"),
+            optimization,
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// Should modules be merged and optimized together before linking?
+    fn lto(&self) -> bool {
+        self.optimization != OptimizationLevel::None
+    }
+
+    /// Bitcode of runtime, that is merged with modules for link-time optimization.
+    /// It's placed next to the runtime library
+    fn runtime_bitcode(&self) -> PathBuf {
+        self.runtime.join("runtime.bc")
+    }
+
+    /// Create target machine for these options
+    fn target_machine(&self) -> miette::Result<TargetMachine> {
+        target_machine(self.target.as_deref(), self.optimization.into()).map_err(|e| miette!("{e}"))
+    }
+
+    /// Operating system of compilation target
+    fn os(&self) -> TargetOs {
+        self.target
+            .as_deref()
+            .map_or_else(TargetOs::host, TargetOs::from_triple)
+    }
+}
+
+impl From<OptimizationLevel> for inkwell::OptimizationLevel {
+    fn from(level: OptimizationLevel) -> Self {
+        match level {
+            OptimizationLevel::None => Self::None,
+            OptimizationLevel::Less => Self::Less,
+            OptimizationLevel::Default => Self::Default,
+            OptimizationLevel::Aggressive => Self::Aggressive,
+        }
+    }
+}
+
+/// Code, that should be generated for a module
+struct CodegenJob {
+    /// Module to generate code for
+    module: Module,
+    /// Should `main` function be generated
+    with_main: bool,
+    /// Kind of generated code.
+    /// One of [`Bitcode`](OutputType::Bitcode), [`Object`](OutputType::Object)
+    /// or [`Assembler`](OutputType::Assembler)
+    output_type: OutputType,
+    /// Where to write generated code
+    output: PathBuf,
+}
+
+impl CodegenJob {
+    /// Lower module to IR and write generated code
+    fn run(&self, compiler: &Compiler, options: &EmitOptions) -> miette::Result<()> {
+        let data = self.module.data(compiler);
+        let llvm = inkwell::context::Context::create();
+        let ir = data.to_ir(&llvm, self.with_main, self.module);
+        debug!(target: "ir", "{}", ir.to_string());
+
+        let machine = options.target_machine()?;
+        set_target(&ir, &machine);
+        if !options.debug_info {
+            ir.strip_debug_info();
+        }
+        if options.optimization != OptimizationLevel::None {
+            optimize(&ir, &machine, "default", options.optimization.as_number())
+                .map_err(|e| miette!("{e}"))?;
+        }
+        // Bitcode isn't machine code yet, so it is instrumented later
+        if options.sanitize && self.output_type != OutputType::Bitcode {
+            sanitize_address(&ir, &machine).map_err(|e| miette!("{e}"))?;
+        }
+
+        trace!(target: "steps", "generating code for {} => {}", data.source_file().path().display(), self.output.display());
+        let file_type = match self.output_type {
+            OutputType::Bitcode => {
+                if !ir.write_bitcode_to_path(&self.output) {
+                    bail!("Can't write bitcode to {}", self.output.display());
+                }
+                return Ok(());
+            }
+            OutputType::Object => FileType::Object,
+            OutputType::Assembler => FileType::Assembly,
+            _ => unreachable!("{:?} is not generated per module", self.output_type),
+        };
+        machine
+            .write_to_file(&ir, file_type, &self.output)
+            .map_err(|e| miette!("Can't write {}: {e}", self.output.display()))
+    }
+}
+
+/// Generate code for modules on up to `jobs` threads.
+/// If cache is present, cached code is reused or stored for later builds.
+///
+/// Paths to generated code are returned in the same order as `modules`
+fn emit_modules(
+    compiler: &Compiler,
+    modules: &[(Module, bool)],
+    output_type: OutputType,
+    dir: &Path,
+    mut cache: Option<&mut Cache>,
+    options: &EmitOptions,
+) -> miette::Result<Vec<PathBuf>> {
+    let mut outputs = Vec::with_capacity(modules.len());
+    let mut pending = Vec::new();
+    for &(module, with_main) in modules {
+        let output = match cache.as_deref_mut() {
+            Some(cache) => {
+                let output = cache.artifact(
+                    compiler,
+                    module,
+                    &options.tags(with_main),
+                    output_type.extension_for(options.os()),
+                );
+                if output.exists() {
+                    trace!(target: "steps", "using cached code for {} => {}", module.data(compiler).source_file().path().display(), output.display());
+                    outputs.push(output);
+                    continue;
+                }
+                output
+            }
+            None => dir.join(format!(
+                "{}.{}",
+                module.data(compiler).name(),
+                output_type.extension_for(options.os())
+            )),
+        };
+        outputs.push(output.clone());
+        pending.push(CodegenJob {
+            module,
+            with_main,
+            output_type,
+            output,
+        });
+    }
+
+    let next = AtomicUsize::new(0);
+    let threads = options.jobs.get().min(pending.len());
+    std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(|| -> miette::Result<()> {
+                    while let Some(job) = pending.get(next.fetch_add(1, Ordering::Relaxed)) {
+                        job.run(compiler, options)?;
+                    }
+                    Ok(())
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .map(|worker| worker.join().expect("code generation thread panicked"))
+            .collect::<miette::Result<()>>()
+    })?;
+
+    Ok(outputs)
+}
+
+/// Merge bitcode of modules and runtime into single module,
+/// optimize it as a whole and write object file
+fn link_time_optimize(
+    bitcodes: &[PathBuf],
+    object: &Path,
+    options: &EmitOptions,
+) -> miette::Result<()> {
+    let llvm = inkwell::context::Context::create();
+    let parse = |bitcode: &Path| {
+        inkwell::module::Module::parse_bitcode_from_path(bitcode, &llvm)
+            .map_err(|e| miette!("Can't read bitcode {}: {e}", bitcode.display()))
+    };
+
+    let runtime = options.runtime_bitcode();
+    if !runtime.exists() {
+        bail!(
+            "No runtime bitcode found at {}, but it's required for optimized builds. \
+             Use --build-runtime to build runtime with bitcode from source",
+            runtime.display()
+        );
+    }
+
+    let (first, rest) = bitcodes.split_first().expect("no modules to link");
+    let merged = parse(first)?;
+    for bitcode in rest
+        .iter()
+        .map(PathBuf::as_path)
+        .chain(std::iter::once(runtime.as_path()))
+    {
+        trace!(target: "steps", "merging {}", bitcode.display());
+        merged
+            .link_in_module(parse(bitcode)?)
+            .map_err(|e| miette!("Can't link {}: {e}", bitcode.display()))?;
+    }
+
+    let machine = options.target_machine()?;
+    set_target(&merged, &machine);
+    optimize(&merged, &machine, "lto", options.optimization.as_number())
+        .map_err(|e| miette!("{e}"))?;
+    if options.sanitize {
+        sanitize_address(&merged, &machine).map_err(|e| miette!("{e}"))?;
+    }
+
+    trace!(target: "steps", "generating code for merged modules => {}", object.display());
+    machine
+        .write_to_file(&merged, FileType::Object, object)
+        .map_err(|e| miette!("Can't write {}: {e}", object.display()))
+}
+
+/// Collect `@test` functions of modules and write object with harness, that runs them
+fn emit_test_harness(
+    compiler: &Compiler,
+    modules: &[Module],
+    object: &Path,
+    options: &EmitOptions,
+) -> miette::Result<()> {
+    let mut tests = vec![];
+    for &module in modules {
+        let data = module.data(compiler);
+        // Executing module would run its statements, so only global variables are initialized
+        let initializer = format!("{}.initialize", data.name());
+        for function in data.iter_functions() {
+            let function = function.read().unwrap();
+            if !function.is_test || function.module != module {
+                continue;
+            }
+
+            let source_file = data.source_file();
+            tests.push(TestCase {
+                name: function.name().to_string(),
+                function: function.mangled_name().to_string(),
+                initializer: initializer.clone(),
+                location: format!(
+                    "{}:{}:{}",
+                    source_file.path().display(),
+                    source_file.line_number(function.start()).one_based(),
+                    source_file.column_number(function.start()).one_based(),
+                ),
+            });
+        }
     }
+    trace!(target: "steps", "generating harness for {} tests", tests.len());
+
+    let llvm = inkwell::context::Context::create();
+    let harness = test_harness(&llvm, &tests);
+    let machine = options.target_machine()?;
+    set_target(&harness, &machine);
+    machine
+        .write_to_file(&harness, FileType::Object, object)
+        .map_err(|e| miette!("Can't write {}: {e}", object.display()))
 }
 
 trait Emit {
@@ -65,6 +519,8 @@ trait Emit {
         output_dir: PathBuf,
         output_type: OutputType,
         dependencies_dir: PathBuf,
+        cache: Option<&mut Cache>,
+        options: &EmitOptions,
     ) -> miette::Result<PathBuf>;
 }
 
@@ -75,15 +531,25 @@ impl Emit for Package {
         output_dir: PathBuf,
         output_type: OutputType,
         dependencies_dir: PathBuf,
+        mut cache: Option<&mut Cache>,
+        options: &EmitOptions,
     ) -> miette::Result<PathBuf> {
         let name = &self.data(compiler).name;
-        let filename = output_type.named(name);
+        let filename = if options.tests {
+            output_type.named_for(&format!("{name}-test"), options.os())
+        } else {
+            output_type.named_for(name, options.os())
+        };
         let output_file = output_dir
             .canonicalize()
             .map_err(|e| miette!("Can't canonicalize output folder: {e}"))?
             .join(&filename);
 
         let dependencies = self.data(compiler).dependencies.clone();
+        let dependencies_options = EmitOptions {
+            tests: false,
+            ..options.clone()
+        };
         let dependencies: Vec<_> = dependencies
             .iter()
             .map(|package| {
@@ -92,6 +558,8 @@ impl Emit for Package {
                     dependencies_dir.clone(),
                     OutputType::DynamicLibrary,
                     dependencies_dir.clone(),
+                    cache.as_deref_mut(),
+                    &dependencies_options,
                 )
             })
             .try_collect()?;
@@ -107,12 +575,16 @@ impl Emit for Package {
             return Ok(output_file);
         }
 
-        let with_main = output_type == OutputType::Executable;
+        // Test harness has its own `main`
+        let with_main = output_type == OutputType::Executable && !options.tests;
 
-        let llvm = inkwell::context::Context::create();
-        let ir = module.data(compiler).to_ir(&llvm, with_main, module);
-        debug!(target: "ir", "{}", ir.to_string());
         if output_type == OutputType::IR {
+            let llvm = inkwell::context::Context::create();
+            let ir = module.data(compiler).to_ir(&llvm, with_main, module);
+            if options.target.is_some() {
+                set_target(&ir, &options.target_machine()?);
+            }
+            debug!(target: "ir", "{}", ir.to_string());
             fs::write(&output_file, ir.to_string())
                 .map_err(|e| miette!("Can't write {output_file:?}: {e}"))?;
             return Ok(output_file);
@@ -120,89 +592,131 @@ impl Emit for Package {
 
         let temp_dir = TempDir::new("ppl").map_err(|e| miette!("Can't create tmp folder: {e}"))?;
 
-        let bitcode = temp_dir.path().join(filename).with_extension("bc");
-        let path = module
-            .data(compiler)
-            .source_file()
-            .path()
-            .to_string_lossy()
-            .into_owned();
-        trace!(target: "steps", "generating bitcode for {} => {}", path, bitcode.display());
-
-        ir.write_bitcode_to_path(&bitcode);
-        if output_type == OutputType::Bitcode {
-            fs::copy(&bitcode, &output_file).map_err(|e| {
+        if matches!(
+            output_type,
+            OutputType::Bitcode | OutputType::Object | OutputType::Assembler
+        ) {
+            let outputs = emit_modules(
+                compiler,
+                &[(module, with_main)],
+                output_type,
+                temp_dir.path(),
+                cache,
+                options,
+            )?;
+            let output = outputs.first().unwrap();
+            fs::copy(output, &output_file).map_err(|e| {
                 miette!(
                     "Can't copy {} to {}: {e}",
-                    bitcode.display(),
+                    output.display(),
                     output_file.display()
                 )
             })?;
             return Ok(output_file);
         }
 
-        let bitcodes: Vec<_> = self.data(compiler)
+        // Main module goes last to keep link order stable
+        let modules: Vec<_> = self
+            .data(compiler)
             .modules
             .iter()
             .filter(|m| **m != module)
-            .map(|m| {
-                let compilation_module = m.clone();
-                let m = m.data(compiler);
-                let llvm = inkwell::context::Context::create();
-                let with_main = false;
-                let ir = m.to_ir(&llvm, with_main, compilation_module);
-                let filename = m.name().to_string();
-                let bitcode = temp_dir.path().join(filename).with_extension("bc");
-                trace!(target: "steps", "generating bitcode for {} => {}", m.source_file().path().to_string_lossy(), bitcode.display());
-                ir.write_bitcode_to_path(&bitcode);
-                bitcode.to_string_lossy().to_string()
-            })
-            .chain(std::iter::once(bitcode.to_string_lossy().to_string()))
+            .map(|m| (*m, false))
+            .chain(std::iter::once((module, with_main)))
             .collect();
+        let mut objects = if options.lto() {
+            let bitcodes = emit_modules(
+                compiler,
+                &modules,
+                OutputType::Bitcode,
+                temp_dir.path(),
+                cache,
+                options,
+            )?;
+            let object = temp_dir
+                .path()
+                .join(&filename)
+                .with_extension(OutputType::Object.extension_for(options.os()));
+            link_time_optimize(&bitcodes, &object, options)?;
+            vec![object]
+        } else {
+            emit_modules(
+                compiler,
+                &modules,
+                OutputType::Object,
+                temp_dir.path(),
+                cache,
+                options,
+            )?
+        };
 
-        let mut clang = std::process::Command::new("clang-18");
-
-        let clang_version = run_fun!("clang-18" "--version")
-            .map_err(|e| miette!("Valid clang-18 installation not found in path: {e}"))?;
-        debug!(target: "clang", "clang version: {}", clang_version);
-
-        let lib_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("target/debug/deps");
-        let lib = lib_path.to_str().unwrap();
-
-        trace!(target: "steps", "assembling {}", output_file.display());
-        let command = match output_type {
-            OutputType::HIR => unreachable!("HIR is already written"),
-            OutputType::IR => unreachable!("IR is already written"),
-            OutputType::Bitcode => unreachable!("IR is already written"),
+        if options.tests {
+            let harness = temp_dir
+                .path()
+                .join("harness")
+                .with_extension(OutputType::Object.extension_for(options.os()));
+            emit_test_harness(compiler, &self.data(compiler).modules, &harness, options)?;
+            objects.push(harness);
+        }
 
-            OutputType::Object => clang.arg("-c"),
-            OutputType::Assembler => clang.arg("-S"),
-            OutputType::StaticLibrary => clang.args(&["-c", "-fPIC"]),
-            OutputType::DynamicLibrary => {
-                if cfg!(target_os = "macos") {
-                    clang.arg("-dynamiclib")
+        trace!(target: "steps", "linking {}", output_file.display());
+        let mut command = match output_type {
+            OutputType::StaticLibrary => {
+                let mut ar = std::process::Command::new("ar");
+                ar.arg("rcs").arg(&output_file).args(&objects);
+                ar
+            }
+            OutputType::DynamicLibrary | OutputType::Executable => {
+                let cc = std::env::var_os("CC").unwrap_or("cc".into());
+                let mut cc = std::process::Command::new(cc);
+                if output_type == OutputType::DynamicLibrary {
+                    if options.os() == TargetOs::MacOS {
+                        cc.arg("-dynamiclib");
+                    } else {
+                        cc.args(&["-shared", "-fPIC"]);
+                    }
+                }
+                if let Some(target) = &options.target {
+                    cc.arg(format!("--target={target}"));
+                }
+                if let Some(sysroot) = &options.sysroot {
+                    cc.arg(format!("--sysroot={}", sysroot.display()));
+                }
+                cc.args(&objects).args(&dependencies);
+                // Code of runtime is already merged into objects by link-time optimization,
+                // so only its dependencies are taken from the static library
+                if options.target.is_some() || options.lto() {
+                    cc.arg(
+                        options
+                            .runtime
+                            .join(OutputType::StaticLibrary.named_for("runtime", options.os())),
+                    );
                 } else {
-                    clang.args(&["-shared", "-fPIC"])
+                    cc.arg("-L")
+                        .arg(&options.runtime)
+                        .arg("-lruntime")
+                        .arg(format!("-Wl,-rpath,{}", options.runtime.display()));
+                }
+                if options.debug_info {
+                    cc.arg("-g");
                 }
+                if options.sanitize {
+                    cc.arg("-fsanitize=address");
+                }
+                cc.arg("-o").arg(&output_file);
+                cc
             }
-            OutputType::Executable => &mut clang,
-        }
-        .args(&["-L", lib, "-lruntime"])
-        .args(&bitcodes)
-        .args(dependencies)
-        .arg("-Wno-override-module")
-        .arg("-g")
-        .arg("-fsanitize=address")
-        .args(&["-o", output_file.to_str().unwrap()]);
+            _ => unreachable!("{output_type:?} is already written"),
+        };
 
         trace!(target: "steps", "running {:?}", command);
         command
             .status()
-            .map_err(|e| miette!("Error while assembling {}: {e}", output_file.display()))?
+            .map_err(|e| miette!("Error while linking {}: {e}", output_file.display()))?
             .exit_ok()
             .map_err(|e| {
                 miette!(
-                    "Error in exit status for assembling {}: {e}",
+                    "Error in exit status for linking {}: {e}",
                     output_file.display()
                 )
             })?;
@@ -210,3 +724,92 @@ impl Emit for Package {
         Ok(output_file)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, num::NonZeroUsize, path::Path};
+
+    use tempdir::TempDir;
+
+    use crate::{
+        compilation::{Compiler, Module},
+        driver::commands::compile::{OptimizationLevel, OutputType},
+    };
+
+    use super::{emit_modules, EmitOptions};
+
+    /// Compile `multifile` test package and return its `greet` and `main` modules
+    fn multifile() -> (Compiler, [(Module, bool); 2]) {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/tests/multifile");
+        let mut compiler = Compiler::new().at(root);
+        let main = compiler.compile("main").unwrap();
+        let greet = compiler.compile("greet").unwrap();
+        (compiler, [(greet, false), (main, true)])
+    }
+
+    /// Generate objects for modules and read them
+    fn objects(
+        compiler: &Compiler,
+        modules: &[(Module, bool)],
+        jobs: usize,
+        target: Option<&str>,
+    ) -> Vec<(std::ffi::OsString, Vec<u8>)> {
+        let dir = TempDir::new("ppl").unwrap();
+        let options = EmitOptions {
+            jobs: NonZeroUsize::new(jobs).unwrap(),
+            debug_info: false,
+            sanitize: false,
+            optimization: OptimizationLevel::None,
+            runtime: dir.path().to_path_buf(),
+            target: target.map(str::to_string),
+            sysroot: None,
+            tests: false,
+        };
+        let outputs = emit_modules(
+            compiler,
+            modules,
+            OutputType::Object,
+            dir.path(),
+            None,
+            &options,
+        )
+        .unwrap();
+        outputs
+            .iter()
+            .map(|output| {
+                let name = output.file_name().unwrap().to_owned();
+                (name, fs::read(output).unwrap())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parallel_codegen_is_deterministic() {
+        let (compiler, modules) = multifile();
+
+        assert_eq!(
+            objects(&compiler, &modules, 1, None),
+            objects(&compiler, &modules, 4, None)
+        );
+    }
+
+    #[test]
+    fn aarch64_objects() {
+        let (compiler, modules) = multifile();
+
+        for (_, object) in objects(&compiler, &modules, 1, Some("aarch64-unknown-linux-gnu")) {
+            assert_eq!(&object[..4], b"\x7fELF");
+            // `e_machine` is `EM_AARCH64`
+            assert_eq!(u16::from_le_bytes([object[18], object[19]]), 183);
+        }
+    }
+
+    #[test]
+    fn wasm32_objects() {
+        let (compiler, modules) = multifile();
+
+        for (_, object) in objects(&compiler, &modules, 1, Some("wasm32-unknown-unknown")) {
+            assert_eq!(&object[..4], b"\0asm");
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use log::trace;
+use miette::miette;
+
+use crate::{compilation::Compiler, driver::commands::Check};
+
+use super::Execute;
+
+impl Execute for Check {
+    type Output = miette::Result<()>;
+
+    /// Run all semantic passes on the package, but don't generate any code
+    fn execute(&self) -> Self::Output {
+        let cwd = std::env::current_dir().map_err(|e| miette!("{e}"))?;
+        let package = cwd.file_name().unwrap().to_str().unwrap();
+
+        let mut compiler = if package == "ppl" {
+            Compiler::without_builtin()
+        } else {
+            Compiler::new()
+        };
+
+        trace!(target: "steps", "checking package `{package}`");
+        self.message_format
+            .report(compiler.compile_package(package))?;
+
+        eprintln!("Finished checking `{package}`");
+        Ok(())
+    }
+}
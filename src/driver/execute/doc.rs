@@ -0,0 +1,189 @@
+use std::{
+    fs,
+    path::Path,
+    process::{Command, Output},
+};
+
+use log::trace;
+use miette::{bail, miette};
+
+use crate::{
+    compilation::Compiler,
+    doc::{markdown::doctests, DocGenerator, ModuleDocs},
+    driver::commands::{compile::OutputType, Doc},
+};
+
+use super::{fmt::ppl_files, Execute};
+
+impl Execute for Doc {
+    type Output = miette::Result<()>;
+
+    /// Generate HTML documentation for the package or run its doctests
+    fn execute(&self) -> Self::Output {
+        let cwd = std::env::current_dir().map_err(|e| miette!("{e}"))?;
+        let package = cwd.file_name().unwrap().to_str().unwrap();
+
+        let mut compiler = if package == "ppl" {
+            Compiler::without_builtin()
+        } else {
+            Compiler::new()
+        };
+
+        trace!(target: "steps", "documenting package `{package}`");
+        let compiled = compiler.compile_package(package)?;
+
+        if self.test {
+            let modules: Vec<_> = compiled
+                .data(&compiler)
+                .modules
+                .iter()
+                .map(|m| ModuleDocs::of(*m, &compiler))
+                .collect();
+            return self.run_doctests(package, &cwd, &modules);
+        }
+
+        let dir = self.output_dir.join("doc").join(package);
+        fs::create_dir_all(&dir).map_err(|e| miette!("Can't create {}: {e}", dir.display()))?;
+
+        for page in DocGenerator::new(compiled, &mut compiler).pages() {
+            let path = dir.join(&page.file_name);
+            fs::write(&path, page.html)
+                .map_err(|e| miette!("Can't write {}: {e}", path.display()))?;
+        }
+
+        eprintln!(
+            "Generated documentation for `{package}` at {}",
+            dir.join("index.html").display()
+        );
+        Ok(())
+    }
+}
+
+impl Doc {
+    /// Compile and run code blocks from documentation comments.
+    /// Each doctest is a separate package, that imports the documented module
+    fn run_doctests(
+        &self,
+        package: &str,
+        root: &Path,
+        modules: &[ModuleDocs],
+    ) -> miette::Result<()> {
+        let mut tests = vec![];
+        for m in modules {
+            for (item, docs) in m.docs() {
+                for (i, code) in doctests(&docs).into_iter().enumerate() {
+                    let name = format!("{} - {item} ({})", m.name, i + 1);
+                    tests.push((m.name.as_str(), name, code));
+                }
+            }
+        }
+
+        println!("\nrunning {} doctests", tests.len());
+        let mut failures = vec![];
+        for (n, (module, name, code)) in tests.iter().enumerate() {
+            let dir = root
+                .join(&self.output_dir)
+                .join("doc/tests")
+                .join(format!("doctest_{n}"));
+            let main = if package == "ppl" {
+                code.clone()
+            } else if *module == "main" {
+                let source = root.join("src/main.ppl");
+                let source = fs::read_to_string(&source)
+                    .map_err(|e| miette!("Can't read {}: {e}", source.display()))?;
+                format!("{source}\n{code}\n")
+            } else {
+                format!("use {module}.*\n\n{code}\n")
+            };
+            let sources = (package != "ppl").then_some(root);
+            prepare_doctest(&dir, &main, sources)?;
+
+            let output = run_doctest(&dir)?;
+            if output.status.success() {
+                println!("test {name} ... ok");
+            } else {
+                println!("test {name} ... FAILED");
+                failures.push((name, output));
+            }
+        }
+
+        if !failures.is_empty() {
+            println!("\nfailures:");
+            for (name, output) in &failures {
+                println!("\n---- {name} ----");
+                print!("{}", String::from_utf8_lossy(&output.stdout));
+                print!("{}", String::from_utf8_lossy(&output.stderr));
+            }
+
+            println!("\nfailures:");
+            for (name, _) in &failures {
+                println!("    {name}");
+            }
+        }
+
+        let result = if failures.is_empty() { "ok" } else { "FAILED" };
+        println!(
+            "\ntest result: {result}. {} passed; {} failed\n",
+            tests.len() - failures.len(),
+            failures.len()
+        );
+
+        if !failures.is_empty() {
+            bail!("{} of {} doctests failed", failures.len(), tests.len());
+        }
+        Ok(())
+    }
+}
+
+/// Create package for doctest with `main` module replaced with doctest.
+/// Sources and dependencies of documented package at `root` are copied, if specified
+fn prepare_doctest(dir: &Path, main: &str, root: Option<&Path>) -> miette::Result<()> {
+    let _ = fs::remove_dir_all(dir);
+
+    if let Some(src) = root.map(|root| root.join("src")).filter(|src| src.is_dir()) {
+        let mut files = vec![];
+        ppl_files(&src, &mut files)?;
+
+        for file in files {
+            let to = dir.join("src").join(file.strip_prefix(&src).unwrap());
+            fs::create_dir_all(to.parent().unwrap())
+                .map_err(|e| miette!("Can't create {}: {e}", dir.display()))?;
+            fs::copy(&file, &to).map_err(|e| miette!("Can't copy {}: {e}", file.display()))?;
+        }
+    }
+
+    let main_path = dir.join("src/main.ppl");
+    fs::create_dir_all(main_path.parent().unwrap())
+        .map_err(|e| miette!("Can't create {}: {e}", dir.display()))?;
+    fs::write(&main_path, main).map_err(|e| miette!("Can't write {}: {e}", main_path.display()))?;
+
+    #[cfg(unix)]
+    if let Some(root) = root {
+        let dependencies = root.join("dependencies");
+        if dependencies.is_dir() {
+            std::os::unix::fs::symlink(&dependencies, dir.join("dependencies"))
+                .map_err(|e| miette!("Can't link {}: {e}", dependencies.display()))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Build doctest package with this compiler and run it
+fn run_doctest(dir: &Path) -> miette::Result<Output> {
+    let ppl = std::env::current_exe().map_err(|e| miette!("{e}"))?;
+    let build = Command::new(&ppl)
+        .arg("build")
+        .current_dir(dir)
+        .output()
+        .map_err(|e| miette!("Can't run {}: {e}", ppl.display()))?;
+    if !build.status.success() {
+        return Ok(build);
+    }
+
+    let name = dir.file_name().unwrap().to_str().unwrap();
+    let exe = dir.join("target").join(OutputType::Executable.named(name));
+    Command::new(&exe)
+        .output()
+        .map_err(|e| miette!("Can't run {}: {e}", exe.display()))
+}
@@ -0,0 +1,26 @@
+use miette::bail;
+
+use crate::{
+    driver::commands::Explain,
+    explain::{codes, explanation},
+};
+
+use super::Execute;
+
+impl Execute for Explain {
+    type Output = miette::Result<()>;
+
+    /// Print explanation of error code or list explained codes
+    fn execute(&self) -> Self::Output {
+        let Some(code) = &self.code else {
+            codes().for_each(|code| println!("{code}"));
+            return Ok(());
+        };
+
+        match explanation(code) {
+            Some(page) => print!("{page}"),
+            None => bail!("no explanation for `{code}`"),
+        }
+        Ok(())
+    }
+}
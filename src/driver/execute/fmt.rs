@@ -0,0 +1,76 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use miette::{bail, miette, NamedSource};
+
+use crate::{
+    ast::{format_code, normalize_indentation},
+    driver::commands::Fmt,
+};
+
+use super::Execute;
+
+impl Execute for Fmt {
+    type Output = miette::Result<()>;
+
+    /// Format files or check that they are already formatted
+    fn execute(&self) -> Self::Output {
+        let files = if self.files.is_empty() {
+            let mut files = vec![];
+            ppl_files(Path::new("src"), &mut files)?;
+            files
+        } else {
+            self.files.clone()
+        };
+
+        let mut unformatted = 0;
+        for file in &files {
+            let source = fs::read_to_string(file)
+                .map_err(|e| miette!("Can't read {}: {e}", file.display()))?;
+            let formatted = format_code(&source).map_err(|e| {
+                miette::Report::from(e).with_source_code(NamedSource::new(
+                    file.to_string_lossy(),
+                    normalize_indentation(&source),
+                ))
+            })?;
+
+            if formatted == source {
+                continue;
+            }
+
+            if self.check {
+                println!("{} is not formatted", file.display());
+                unformatted += 1;
+            } else {
+                fs::write(file, formatted)
+                    .map_err(|e| miette!("Can't write {}: {e}", file.display()))?;
+            }
+        }
+
+        if unformatted > 0 {
+            bail!("{unformatted} of {} files are not formatted", files.len());
+        }
+        Ok(())
+    }
+}
+
+/// Collect `.ppl` files in directory and its subdirectories
+pub(super) fn ppl_files(dir: &Path, files: &mut Vec<PathBuf>) -> miette::Result<()> {
+    let mut entries: Vec<_> = fs::read_dir(dir)
+        .map_err(|e| miette!("Can't read {}: {e}", dir.display()))?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .try_collect()
+        .map_err(|e| miette!("Can't read {}: {e}", dir.display()))?;
+    entries.sort();
+
+    for path in entries {
+        if path.is_dir() {
+            ppl_files(&path, files)?;
+        } else if path.extension().is_some_and(|ext| ext == "ppl") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,12 @@
+use crate::driver::commands::Lsp;
+
+use super::Execute;
+
+impl Execute for Lsp {
+    type Output = miette::Result<()>;
+
+    /// Serve language server protocol over stdin and stdout
+    fn execute(&self) -> Self::Output {
+        crate::lsp::run_stdio()
+    }
+}
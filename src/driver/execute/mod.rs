@@ -1,8 +1,18 @@
 mod build;
+mod check;
+mod doc;
+mod explain;
+mod fmt;
+mod lsp;
 mod new;
 mod run;
+mod test;
 
-use super::Command;
+use miette::miette;
+
+use crate::JsonReporter;
+
+use super::{commands::compile::MessageFormat, Command};
 
 /// Trait for executing commands
 pub trait Execute {
@@ -20,7 +30,35 @@ impl Execute for Command {
         match self {
             Command::New(new) => new.execute(),
             Command::Build(build) => build.execute().map(|_| {}),
+            Command::Check(check) => check.execute(),
             Command::Run(run) => run.execute(),
+            Command::Test(test) => test.execute(),
+            Command::Fmt(fmt) => fmt.execute(),
+            Command::Doc(doc) => doc.execute(),
+            Command::Lsp(lsp) => lsp.execute(),
+            Command::Explain(explain) => explain.execute(),
+        }
+    }
+}
+
+impl MessageFormat {
+    /// Report error of a command in this format.
+    ///
+    /// JSON diagnostics are printed to stdout,
+    /// so only a summary is left in the returned error
+    pub fn report<T>(&self, result: miette::Result<T>) -> miette::Result<T> {
+        let error = match result {
+            Err(error) if *self == MessageFormat::Json => error,
+            result => return result,
+        };
+
+        let diagnostics = JsonReporter.render(error.as_ref());
+        for diagnostic in &diagnostics {
+            println!("{diagnostic}");
         }
+        Err(miette!(
+            "aborting due to {} previous error(s)",
+            diagnostics.len()
+        ))
     }
 }
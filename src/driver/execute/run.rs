@@ -1,3 +1,5 @@
+use miette::miette;
+
 use crate::driver::commands::{Build, Run};
 
 use super::Execute;
@@ -5,10 +7,27 @@ use super::Execute;
 impl Execute for Run {
     type Output = miette::Result<()>;
 
-    /// Build and run the project
+    /// Build and run the project.
+    /// Exits with the same code as the program, if it fails
     fn execute(&self) -> Self::Output {
-        let exe = Build::default().execute()?;
-        std::process::Command::new(exe).status().unwrap();
+        let exe = Build {
+            output_dir: self.output_dir.clone(),
+            release: self.release,
+            opt_level: self.opt_level,
+            build_runtime: self.build_runtime,
+            message_format: self.message_format,
+            ..Default::default()
+        }
+        .execute()?;
+
+        let status = std::process::Command::new(&exe)
+            .args(&self.args)
+            .status()
+            .map_err(|e| miette!("Can't run {}: {e}", exe.display()))?;
+        if !status.success() {
+            // Programs, killed by signal, have no exit code
+            std::process::exit(status.code().unwrap_or(1));
+        }
         Ok(())
     }
 }
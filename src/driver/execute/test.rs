@@ -0,0 +1,77 @@
+use std::process::{Command, Output};
+
+use miette::{bail, miette};
+
+use crate::driver::commands::{compile::OutputType, Build, Test};
+
+use super::Execute;
+
+impl Execute for Test {
+    type Output = miette::Result<()>;
+
+    /// Build test harness and run each test in a separate process
+    fn execute(&self) -> Self::Output {
+        let harness = Build {
+            output_dir: self.output_dir.clone(),
+            output_type: Some(OutputType::Executable),
+            tests: true,
+            ..Default::default()
+        }
+        .execute()?;
+
+        let run = |args: &[&str]| -> miette::Result<Output> {
+            Command::new(&harness)
+                .args(args)
+                .output()
+                .map_err(|e| miette!("Can't run {}: {e}", harness.display()))
+        };
+
+        let list = run(&[])?;
+        let list = String::from_utf8_lossy(&list.stdout).into_owned();
+        let (tests, filtered_out): (Vec<_>, Vec<_>) = list
+            .lines()
+            .filter_map(|line| line.split_once('\t'))
+            .partition(|(name, _)| {
+                self.filters.is_empty() || self.filters.iter().any(|f| name.contains(f.as_str()))
+            });
+
+        println!("\nrunning {} tests", tests.len());
+        let mut failures = vec![];
+        for (name, location) in &tests {
+            let output = run(&[name])?;
+            if output.status.success() {
+                println!("test {name} ... ok");
+            } else {
+                println!("test {name} ... FAILED");
+                failures.push((name, location, output));
+            }
+        }
+
+        if !failures.is_empty() {
+            println!("\nfailures:");
+            for (name, location, output) in &failures {
+                println!("\n---- {name} at {location} ----");
+                print!("{}", String::from_utf8_lossy(&output.stdout));
+                print!("{}", String::from_utf8_lossy(&output.stderr));
+            }
+
+            println!("\nfailures:");
+            for (name, ..) in &failures {
+                println!("    {name}");
+            }
+        }
+
+        let result = if failures.is_empty() { "ok" } else { "FAILED" };
+        println!(
+            "\ntest result: {result}. {} passed; {} failed; {} filtered out\n",
+            tests.len() - failures.len(),
+            failures.len(),
+            filtered_out.len()
+        );
+
+        if !failures.is_empty() {
+            bail!("{} of {} tests failed", failures.len(), tests.len());
+        }
+        Ok(())
+    }
+}
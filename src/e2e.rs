@@ -36,6 +36,11 @@ macro_rules! e2e {
             let (run_log, status) = $crate::e2e::internal::run(&tmp, name, &dir);
             assert_snapshot!(concat!(stringify!($name), ".run"), run_log);
             status.exit_ok().map_err(|e| miette!("{e}")).unwrap();
+
+            if cfg!(target_os = "linux") {
+                let leaks = $crate::e2e::internal::leaks(&tmp, name, &dir);
+                assert!(leaks.is_none(), "{name} leaks memory:\n{}", leaks.unwrap());
+            }
         }
     };
 }
@@ -61,6 +66,14 @@ pub mod internal {
 
     const PPL: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/target/debug/ppl");
 
+    /// Input for the program of package at `dir`.
+    /// It's read from `stdin` file next to `main.ppl`, if there is one
+    fn stdin(dir: &Path) -> std::process::Stdio {
+        std::fs::File::open(dir.join("src").join("stdin"))
+            .map(Into::into)
+            .unwrap_or_else(|_| std::process::Stdio::null())
+    }
+
     pub fn compile(temp_dir: &Path, dir: &Path) {
         run_cmd! {
             cd $dir;
@@ -70,6 +83,23 @@ pub mod internal {
         .unwrap();
     }
 
+    /// Build package at `dir` into `temp_dir`, passing `args` to `ppl build`
+    pub fn compile_with_args(temp_dir: &Path, dir: &Path, args: &[&str]) {
+        let output = std::process::Command::new(PPL)
+            .arg("build")
+            .args(&["--output-dir", temp_dir.to_str().unwrap()])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .map_err(|e| miette!("{e}"))
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
     pub fn hir(temp_dir: &Path, _name: &str, dir: &Path) -> Result<String, String> {
         let output = std::process::Command::new(PPL)
             .args(&["build"])
@@ -111,6 +141,7 @@ pub mod internal {
 
         let output = std::process::Command::new(exe)
             .current_dir(&dir)
+            .stdin(stdin(dir))
             .output()
             .map_err(|e| miette!("{e}"))
             .unwrap();
@@ -122,4 +153,196 @@ pub mod internal {
 
         (run_log, output.status)
     }
+
+    /// Build package at `dir` with AddressSanitizer and run it with LeakSanitizer enabled.
+    ///
+    /// Returns report of sanitizer, if program leaks memory
+    pub fn leaks(temp_dir: &Path, name: &str, dir: &Path) -> Option<String> {
+        let output_dir = temp_dir.join("leaks");
+        run_cmd! {
+            cd $dir;
+            $PPL build --output-dir $output_dir --sanitize
+        }
+        .map_err(|e| miette!("{e}"))
+        .unwrap();
+
+        let exe = output_dir.join(OutputType::Executable.named(name));
+        let output = std::process::Command::new(exe)
+            .env("ASAN_OPTIONS", "detect_leaks=1")
+            .current_dir(&dir)
+            .stdin(stdin(dir))
+            .output()
+            .map_err(|e| miette!("{e}"))
+            .unwrap();
+
+        // Programs may fail on purpose, so only sanitizer's report counts as a leak
+        let stderr = String::from_utf8(output.stderr).expect("stderr is not utf8");
+        stderr
+            .contains("ERROR: LeakSanitizer: detected memory leaks")
+            .then_some(stderr)
+    }
+
+    /// Run `ppl run` for package at `dir`, passing `args` to the program
+    pub fn run_with_args(temp_dir: &Path, dir: &Path, args: &[&str]) -> (String, ExitStatus) {
+        let output = std::process::Command::new(PPL)
+            .arg("run")
+            .args(&["--output-dir", temp_dir.to_str().unwrap()])
+            .arg("--")
+            .args(args)
+            .current_dir(dir)
+            .stdin(stdin(dir))
+            .output()
+            .map_err(|e| miette!("{e}"))
+            .unwrap();
+
+        let stdout = String::from_utf8(output.stdout).expect("stdout is not utf8");
+        let stderr = String::from_utf8(output.stderr).expect("stderr is not utf8");
+
+        (format!("{stdout}{stderr}"), output.status)
+    }
+
+    /// Run `ppl check` for package at `dir`
+    pub fn check(dir: &Path) -> (String, ExitStatus) {
+        let output = std::process::Command::new(PPL)
+            .arg("check")
+            .current_dir(dir)
+            .output()
+            .map_err(|e| miette!("{e}"))
+            .unwrap();
+
+        let stderr = String::from_utf8(output.stderr).expect("stderr is not utf8");
+
+        (stderr, output.status)
+    }
+
+    /// Run `ppl check --message-format=json` for package at `dir`.
+    /// Returns reported diagnostics
+    pub fn check_json(dir: &Path) -> (Vec<serde_json::Value>, ExitStatus) {
+        let output = std::process::Command::new(PPL)
+            .args(&["check", "--message-format=json"])
+            .current_dir(dir)
+            .output()
+            .map_err(|e| miette!("{e}"))
+            .unwrap();
+
+        let stdout = String::from_utf8(output.stdout).expect("stdout is not utf8");
+        let diagnostics = stdout
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("diagnostic is not JSON"))
+            .collect();
+
+        (diagnostics, output.status)
+    }
+
+    /// Run `ppl explain` for error code.
+    /// Returns printed explanation
+    pub fn explain(code: &str) -> (String, ExitStatus) {
+        let output = std::process::Command::new(PPL)
+            .args(&["explain", code])
+            .output()
+            .map_err(|e| miette!("{e}"))
+            .unwrap();
+
+        let stdout = String::from_utf8(output.stdout).expect("stdout is not utf8");
+        (stdout, output.status)
+    }
+
+    /// Run `ppl test` for package at `dir` with optional name filters
+    pub fn test(temp_dir: &Path, dir: &Path, filters: &[&str]) -> (String, ExitStatus) {
+        let output = std::process::Command::new(PPL)
+            .arg("test")
+            .args(&["--output-dir", temp_dir.to_str().unwrap()])
+            .args(filters)
+            .current_dir(dir)
+            .output()
+            .map_err(|e| miette!("{e}"))
+            .unwrap();
+
+        let stdout = String::from_utf8(output.stdout).expect("stdout is not utf8");
+        let stderr = String::from_utf8(output.stderr).expect("stderr is not utf8");
+
+        (format!("{stdout}{stderr}"), output.status)
+    }
+
+    /// Run `ppl doc` for package at `dir`, optionally with `--test`
+    pub fn doc(temp_dir: &Path, dir: &Path, test: bool) -> (String, ExitStatus) {
+        let output = std::process::Command::new(PPL)
+            .arg("doc")
+            .args(&["--output-dir", temp_dir.to_str().unwrap()])
+            .args(test.then_some("--test"))
+            .current_dir(dir)
+            .output()
+            .map_err(|e| miette!("{e}"))
+            .unwrap();
+
+        let stdout = String::from_utf8(output.stdout).expect("stdout is not utf8");
+        let stderr = String::from_utf8(output.stderr).expect("stderr is not utf8");
+
+        (format!("{stdout}{stderr}"), output.status)
+    }
+
+    /// Run REPL with `input` and history, saved inside `temp_dir`.
+    /// Returns printed output
+    pub fn repl(temp_dir: &Path, input: &str) -> (String, ExitStatus) {
+        use std::io::Write;
+        use std::process::Stdio;
+
+        let mut repl = std::process::Command::new(PPL)
+            .env("PPL_HISTORY", temp_dir.join("history"))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| miette!("{e}"))
+            .unwrap();
+
+        let mut stdin = repl.stdin.take().unwrap();
+        stdin.write_all(input.as_bytes()).unwrap();
+        drop(stdin);
+
+        let output = repl.wait_with_output().unwrap();
+        let stdout = String::from_utf8(output.stdout).expect("stdout is not utf8");
+        let stderr = String::from_utf8(output.stderr).expect("stderr is not utf8");
+
+        (format!("{stdout}{stderr}"), output.status)
+    }
+
+    /// Run `ppl lsp` session with scripted JSON-RPC messages.
+    /// Returns all messages, sent by server
+    pub fn lsp(messages: &[serde_json::Value]) -> Vec<serde_json::Value> {
+        use std::io::Write;
+        use std::process::Stdio;
+
+        let mut server = std::process::Command::new(PPL)
+            .arg("lsp")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| miette!("{e}"))
+            .unwrap();
+
+        let mut stdin = server.stdin.take().unwrap();
+        for message in messages {
+            let content = message.to_string();
+            write!(stdin, "Content-Length: {}\r\n\r\n{content}", content.len()).unwrap();
+        }
+        drop(stdin);
+
+        let output = server.wait_with_output().unwrap();
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(output.status.success(), "{stderr}");
+
+        let mut stdout = String::from_utf8(output.stdout).expect("stdout is not utf8");
+        let mut responses = vec![];
+        while let Some((header, rest)) = stdout.split_once("\r\n\r\n") {
+            let length: usize = header
+                .trim_start_matches("Content-Length: ")
+                .parse()
+                .expect("invalid header");
+            responses.push(serde_json::from_str(&rest[..length]).unwrap());
+            stdout = rest[length..].to_string();
+        }
+        responses
+    }
 }
@@ -0,0 +1,186 @@
+/// Helper macro to embed explanations of error codes
+macro_rules! explanations {
+    ($($category:ident::$name:ident),* $(,)?) => {
+        /// Explanations of error codes as markdown pages
+        const EXPLANATIONS: &[(&str, &str)] = &[
+            $(
+                (
+                    concat!(stringify!($category), "::", stringify!($name)),
+                    include_str!(concat!(stringify!($category), "/", stringify!($name), ".md")),
+                )
+            ),*
+        ];
+    };
+}
+
+explanations!(
+    lexer::invalid_token,
+    lexer::invalid_indentation,
+    lexer::extra_token,
+    lexer::missing_token,
+    lexer::unexpected_token,
+    parser::missing_expression,
+    parser::missing_declaration,
+    parser::missing_statement,
+    parser::missing_variable_name,
+    parser::empty_block,
+    semantics::undefined_variable,
+    semantics::unknown_type,
+    semantics::unknown_annotation,
+    semantics::invalid_test,
+    semantics::assignment_to_immutable,
+    semantics::type_mismatch,
+    semantics::literal_out_of_range,
+    semantics::condition_type_mismatch,
+    semantics::no_unary_operator,
+    semantics::no_function,
+    semantics::return_outside_function,
+    semantics::missing_return_value,
+    semantics::return_type_mismatch,
+    semantics::cant_deduce_return_type,
+    semantics::cant_deduce_type,
+    semantics::no_member,
+    semantics::multiple_initialization,
+    semantics::missing_fields,
+    semantics::non_class_constructor,
+    semantics::not_implemented,
+    semantics::reference_mut_to_immutable,
+    semantics::unresolved_import,
+);
+
+/// Get explanation of error code.
+///
+/// Code may be given without its category, like `no_function`
+pub fn explanation(code: &str) -> Option<&'static str> {
+    EXPLANATIONS
+        .iter()
+        .find(|(c, _)| *c == code || c.split_once("::").is_some_and(|(_, name)| name == code))
+        .map(|(_, page)| *page)
+}
+
+/// Error codes, that have explanations
+pub fn codes() -> impl Iterator<Item = &'static str> {
+    EXPLANATIONS.iter().map(|(code, _)| *code)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use miette::{Diagnostic, NamedSource};
+
+    use super::*;
+    use crate::{
+        ast,
+        compilation::Compiler,
+        doc::markdown::{blocks, Block},
+        SourceFile,
+    };
+
+    /// Codes of diagnostics, declared in error modules
+    fn declared_codes() -> Vec<String> {
+        let sources = [
+            include_str!("../syntax/error.rs"),
+            include_str!("../semantics/error.rs"),
+        ];
+        sources
+            .iter()
+            .flat_map(|source| source.lines())
+            .filter_map(|line| {
+                let line = line.trim();
+                if let Some(rest) = line.strip_prefix("#[diagnostic(code(") {
+                    return rest.split(')').next().map(str::to_string);
+                }
+                // Codes of manually implemented diagnostics
+                let rest = line.strip_prefix("Some(Box::new(\"")?;
+                rest.split('"')
+                    .next()
+                    .filter(|code| code.contains("::"))
+                    .map(str::to_string)
+            })
+            .collect()
+    }
+
+    /// Collect codes of diagnostic and diagnostics it refers to
+    fn collect_codes(diagnostic: &dyn Diagnostic, codes: &mut Vec<String>) {
+        codes.extend(diagnostic.code().map(|code| code.to_string()));
+        for related in diagnostic.related().into_iter().flatten() {
+            collect_codes(related, codes);
+        }
+        if let Some(source) = diagnostic.diagnostic_source() {
+            collect_codes(source, codes);
+        }
+    }
+
+    /// Codes of errors, reported for example
+    fn reported_codes(code: &str, example: &str, compiler: &mut Compiler) -> Vec<String> {
+        let mut codes = Vec::new();
+        // These errors can't appear in modules,
+        // as they are reported only when text must contain a single item
+        match code {
+            "lexer::extra_token" => {
+                if let Err(err) = ast::Expression::from_str(example) {
+                    collect_codes(&err, &mut codes);
+                }
+            }
+            "parser::missing_declaration" => {
+                if let Err(err) = ast::Declaration::from_str(example) {
+                    collect_codes(&err, &mut codes);
+                }
+            }
+            _ => {
+                let name = format!("{}.ppl", code.replace("::", "_"));
+                let source_file =
+                    SourceFile::in_memory(NamedSource::new(name, example.to_string()));
+                if let Err(err) = compiler.compile_source(source_file) {
+                    collect_codes(err.as_ref(), &mut codes);
+                }
+            }
+        }
+        codes
+    }
+
+    #[test]
+    fn every_code_is_explained() {
+        let codes = declared_codes();
+        assert!(!codes.is_empty());
+
+        for code in codes {
+            let page = explanation(&code);
+            assert!(page.is_some(), "no explanation for `{code}`");
+            assert!(page.unwrap().starts_with(&format!("# {code}\n")));
+        }
+    }
+
+    #[test]
+    fn examples_report_their_codes() {
+        // Declared, but not reported by compiler yet
+        let unreported = ["semantics::no_unary_operator"];
+
+        let mut compiler = Compiler::new();
+        for code in codes().filter(|code| !unreported.contains(code)) {
+            let example = blocks(explanation(code).unwrap())
+                .into_iter()
+                .find_map(|block| match block {
+                    Block::Code { code, .. } => Some(code),
+                    Block::Paragraph(_) => None,
+                })
+                .unwrap_or_else(|| panic!("no example for `{code}`"));
+
+            let reported = reported_codes(code, &example, &mut compiler);
+            assert!(
+                reported.iter().any(|c| c == code),
+                "example for `{code}` reported {reported:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn short_codes() {
+        assert_eq!(
+            explanation("no_function"),
+            explanation("semantics::no_function")
+        );
+        assert!(explanation("semantics::no_such_code").is_none());
+    }
+}
@@ -5,4 +5,6 @@ pub enum Annotation {
     MangleAs(String),
     /// Mark type declaration as builtin
     Builtin,
+    /// Mark function as test
+    Test,
 }
@@ -256,6 +256,12 @@ pub struct FunctionData {
     /// Mangled name to use instead of default
     #[drive(skip)]
     pub(crate) mangled_name: Option<String>,
+    /// Is this function marked with `@test`?
+    #[drive(skip)]
+    pub is_test: bool,
+    /// Documentation comment of function
+    #[drive(skip)]
+    pub docs: Option<String>,
     /// Cached format for name of function
     #[drive(skip)]
     pub(crate) name_format: String,
@@ -385,7 +391,7 @@ impl Typed for FunctionData {
 
 impl Display for FunctionData {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if !self.body.is_empty() || self.mangled_name.is_some() {
+        if !self.body.is_empty() || self.mangled_name.is_some() || self.is_test {
             writeln!(f, "")?;
         }
 
@@ -393,6 +399,10 @@ impl Display for FunctionData {
             writeln!(f, "@mangle_as({name:?})")?;
         }
 
+        if self.is_test {
+            writeln!(f, "@test")?;
+        }
+
         let indent = "\t".repeat(f.width().unwrap_or(0));
         write!(f, "{indent}")?;
 
@@ -442,6 +452,10 @@ pub struct FunctionBuilder {
     name_parts: Vec<FunctionNamePart>,
     /// Mangled name of function
     mangled_name: Option<String>,
+    /// Is this a test function?
+    is_test: bool,
+    /// Documentation comment of function
+    docs: Option<String>,
     /// Body of a function
     body: Vec<Statement>,
 }
@@ -455,6 +469,8 @@ impl FunctionBuilder {
             generic_types: Vec::new(),
             name_parts: Vec::new(),
             mangled_name: None,
+            is_test: false,
+            docs: None,
             body: vec![],
         }
     }
@@ -477,6 +493,18 @@ impl FunctionBuilder {
         self
     }
 
+    /// Mark function as test
+    pub fn with_test(mut self, is_test: bool) -> Self {
+        self.is_test = is_test;
+        self
+    }
+
+    /// Set documentation comment of function
+    pub fn with_docs(mut self, docs: Option<String>) -> Self {
+        self.docs = docs;
+        self
+    }
+
     /// Set body of function
     pub fn with_body(mut self, body: Vec<Statement>) -> Self {
         self.body = body;
@@ -523,6 +551,8 @@ impl FunctionBuilder {
             name_format,
             name,
             mangled_name: self.mangled_name,
+            is_test: self.is_test,
+            docs: self.docs,
             body: self.body,
         }
     }
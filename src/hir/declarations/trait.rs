@@ -104,6 +104,9 @@ pub struct TraitData {
     /// Module this trait is located in
     #[drive(skip)]
     pub module: Module,
+    /// Documentation comment of trait
+    #[drive(skip)]
+    pub docs: Option<String>,
 }
 
 fn drive_functions<V: VisitorMut>(funcs: &mut IndexMap<String, Function>, visitor: &mut V) {
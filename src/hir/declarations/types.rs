@@ -155,11 +155,21 @@ macro_rules! builtin_class {
 builtin_class! {
     None,
     Bool,
+    Char,
+    I8,
+    I16,
     I32,
+    I64,
+    U8,
+    U16,
+    U32,
+    U64,
+    F32,
     F64,
     Integer,
     Rational,
     String,
+    MemoryAddress,
     Reference,
     ReferenceMut
 }
@@ -171,9 +181,97 @@ impl BuiltinClass {
         match self {
             None => 0,
             Bool => 1,
-            I32 => 4,
-            F64 => 8,
-            Integer | Rational | String | Reference | ReferenceMut => POINTER_SIZE,
+            Char => 4,
+            I8 | I16 | I32 | I64 | U8 | U16 | U32 | U64 | F32 | F64 => {
+                self.fixed_width().unwrap().bits() as usize / 8
+            }
+            Integer | Rational | String | MemoryAddress | Reference | ReferenceMut => POINTER_SIZE,
+        }
+    }
+
+    /// Get kind and width of fixed-width number type
+    pub fn fixed_width(&self) -> Option<FixedWidth> {
+        use BuiltinClass::*;
+        use FixedWidth::*;
+        Some(match self {
+            I8 => Signed(8),
+            I16 => Signed(16),
+            I32 => Signed(32),
+            I64 => Signed(64),
+            U8 => Unsigned(8),
+            U16 => Unsigned(16),
+            U32 => Unsigned(32),
+            U64 => Unsigned(64),
+            F32 => Float(32),
+            F64 => Float(64),
+            _ => return Option::None,
+        })
+    }
+}
+
+/// Builtin number type, that is lowered to LLVM type of fixed width
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum FixedWidth {
+    /// Signed integer with given number of bits
+    Signed(u32),
+    /// Unsigned integer with given number of bits
+    Unsigned(u32),
+    /// Floating point number with given number of bits
+    Float(u32),
+}
+
+impl Display for FixedWidth {
+    /// Display name of type, like `I8` or `F32`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match self {
+            FixedWidth::Signed(_) => "I",
+            FixedWidth::Unsigned(_) => "U",
+            FixedWidth::Float(_) => "F",
+        };
+        write!(f, "{kind}{}", self.bits())
+    }
+}
+
+impl FixedWidth {
+    /// Number of bits in this type
+    pub fn bits(&self) -> u32 {
+        match self {
+            FixedWidth::Signed(bits) | FixedWidth::Unsigned(bits) | FixedWidth::Float(bits) => {
+                *bits
+            }
+        }
+    }
+
+    /// Is this a signed or unsigned integer?
+    pub fn is_integer(&self) -> bool {
+        !self.is_float()
+    }
+
+    /// Is this a floating point number?
+    pub fn is_float(&self) -> bool {
+        matches!(self, FixedWidth::Float(_))
+    }
+
+    /// Can integer `value` be represented by this type without loss?
+    ///
+    /// Floats are considered to represent any integer in their range, as they round it
+    pub fn can_represent(&self, value: &rug::Integer) -> bool {
+        match *self {
+            FixedWidth::Signed(bits) => value.signed_bits() <= bits,
+            FixedWidth::Unsigned(bits) => *value >= 0 && value.significant_bits() <= bits,
+            FixedWidth::Float(32) => value.to_f32().is_finite(),
+            FixedWidth::Float(_) => value.to_f64().is_finite(),
+        }
+    }
+
+    /// Can rational `value` be represented by this type?
+    ///
+    /// Floats round it, so only values out of their range can't be represented
+    pub fn can_represent_rational(&self, value: &rug::Rational) -> bool {
+        match *self {
+            FixedWidth::Float(32) => value.to_f32().is_finite(),
+            FixedWidth::Float(_) => value.to_f64().is_finite(),
+            _ => value.is_integer() && self.can_represent(value.numer()),
         }
     }
 }
@@ -214,11 +312,21 @@ impl Class {
         self.read().unwrap().is_bool()
     }
 
+    /// Is this a builtin `Char` type?
+    pub fn is_char(&self) -> bool {
+        self.read().unwrap().is_char()
+    }
+
     /// Is this a builtin `I32` type?
     pub fn is_i32(&self) -> bool {
         self.read().unwrap().is_i32()
     }
 
+    /// Get kind and width, if this is a builtin fixed-width number type
+    pub fn fixed_width(&self) -> Option<FixedWidth> {
+        self.read().unwrap().fixed_width()
+    }
+
     /// Is this a builtin "Integer" type?
     pub fn is_integer(&self) -> bool {
         self.read().unwrap().is_integer()
@@ -331,6 +439,9 @@ pub struct ClassData {
     pub builtin: Option<BuiltinClass>,
     /// Members of type
     pub members: Vec<Member>,
+    /// Documentation comment of type
+    #[drive(skip)]
+    pub docs: Option<String>,
 }
 
 impl ClassData {
@@ -359,16 +470,26 @@ impl ClassData {
         self.builtin == Some(BuiltinClass::Bool)
     }
 
+    /// Is this a builtin `Char` type?
+    pub fn is_char(&self) -> bool {
+        self.builtin == Some(BuiltinClass::Char)
+    }
+
     /// Is this a builtin `I32` type?
     pub fn is_i32(&self) -> bool {
         self.builtin == Some(BuiltinClass::I32)
     }
 
-    /// Is this a builtin `I32` type?
+    /// Is this a builtin `F64` type?
     pub fn is_f64(&self) -> bool {
         self.builtin == Some(BuiltinClass::F64)
     }
 
+    /// Get kind and width, if this is a builtin fixed-width number type
+    pub fn fixed_width(&self) -> Option<FixedWidth> {
+        self.builtin.as_ref().and_then(BuiltinClass::fixed_width)
+    }
+
     /// Is this a builtin "Integer" type?
     pub fn is_integer(&self) -> bool {
         self.builtin == Some(BuiltinClass::Integer)
@@ -518,6 +639,7 @@ mod tests {
                 generic_parameters: vec![],
                 builtin: None,
                 members: vec![],
+                docs: None,
             }
         );
     }
@@ -552,6 +674,7 @@ mod tests {
                     }
                     .into(),
                 }),],
+                docs: None,
             }
         );
     }
@@ -586,6 +709,7 @@ mod tests {
                         ty: integer,
                     }),
                 ],
+                docs: None,
             }
         );
     }
@@ -43,14 +43,25 @@ pub enum Literal {
     },
 }
 
+/// Suffix of literal of fixed-width number type, like `i8` or `f32`
+fn suffix(ty: &Type) -> String {
+    if ty.fixed_width().is_some() {
+        ty.to_string().to_lowercase()
+    } else {
+        String::new()
+    }
+}
+
 impl Display for Literal {
     /// Display literal
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Literal::None { .. } => write!(f, "none"),
             Literal::Bool { value, .. } => write!(f, "{}", value),
-            Literal::Integer { value, .. } => write!(f, "{}", value),
-            Literal::Rational { value, .. } => write!(f, "{}", maybe_to_decimal_string(value)),
+            Literal::Integer { value, ty, .. } => write!(f, "{}{}", value, suffix(ty)),
+            Literal::Rational { value, ty, .. } => {
+                write!(f, "{}{}", maybe_to_decimal_string(value), suffix(ty))
+            }
             Literal::String { value, .. } => write!(f, "{:?}", value),
         }
     }
@@ -93,9 +104,10 @@ impl Mutable for Literal {
 #[cfg(test)]
 mod tests {
     use crate::{
+        ast,
         compilation::Compiler,
         hir::ModuleData,
-        semantics::{Context, ModuleContext},
+        semantics::{Context, ModuleContext, ToHIR},
     };
 
     use super::*;
@@ -131,6 +143,13 @@ mod tests {
         };
         assert_eq!(format!("{}", literal_integer), "42");
 
+        let literal_i8 = Literal::Integer {
+            span: 0..3,
+            value: rug::Integer::from(42),
+            ty: context.builtin().types().for_suffix("i8"),
+        };
+        assert_eq!(format!("{}", literal_i8), "42i8");
+
         let literal_rational = Literal::Rational {
             span: 0..1,
             value: rug::Rational::from_f32(0.5).unwrap(),
@@ -152,4 +171,21 @@ mod tests {
         };
         assert_eq!(format!("{}", literal_string), r#""hello""#);
     }
+
+    #[test]
+    fn literals_out_of_range() {
+        let mut compiler = Compiler::new();
+        let mut context = ModuleContext::new(ModuleData::default(), &mut compiler);
+        let f32_max = format!("{}.0f32", f32::MAX as u128);
+        let beyond_f32 = format!("{}0.0f32", f32::MAX as u128);
+
+        for text in ["127i8", "255u8", "1.5f32", &f32_max] {
+            let literal = text.parse::<ast::Literal>().unwrap();
+            assert!(literal.to_hir(&mut context).is_ok(), "{text}");
+        }
+        for text in ["128i8", "256u8", &beyond_f32] {
+            let literal = text.parse::<ast::Literal>().unwrap();
+            assert!(literal.to_hir(&mut context).is_err(), "{text}");
+        }
+    }
 }
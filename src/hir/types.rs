@@ -6,7 +6,7 @@ use std::{
 
 use crate::{mutability::Mutable, named::Named, syntax::Identifier, AddSourceLocation};
 
-use super::{Basename, BuiltinClass, Class, Generic, Member, Trait, TypeReference};
+use super::{Basename, BuiltinClass, Class, FixedWidth, Generic, Member, Trait, TypeReference};
 use derive_more::{Display, From, TryInto};
 use derive_visitor::DriveMut;
 use enum_dispatch::enum_dispatch;
@@ -289,6 +289,14 @@ impl Type {
         }
     }
 
+    /// Get kind and width, if this is a builtin fixed-width number type
+    pub fn fixed_width(&self) -> Option<FixedWidth> {
+        match self.without_ref() {
+            Type::Class(c) => c.fixed_width(),
+            _ => None,
+        }
+    }
+
     /// Is this a builtin "Integer" type?
     pub fn is_integer(&self) -> bool {
         match self.without_ref() {
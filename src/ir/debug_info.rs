@@ -113,6 +113,16 @@ impl<'llvm, 's> DebugInfo<'llvm, 's> {
             .create_debug_location(self.llvm, line, column, self.scope(), inlined_at)
     }
 
+    /// Get location in source code, like `src/main.ppl:3:2`
+    pub fn source_location(&self, offset: usize) -> String {
+        format!(
+            "{}:{}:{}",
+            self.source_file.path().display(),
+            self.source_file.line_number(offset).one_based(),
+            self.source_file.column_number(offset).one_based()
+        )
+    }
+
     /// Register function in debug info
     pub fn register_function(&self, f: FunctionValue<'llvm>, at: usize) -> DISubprogram<'llvm> {
         let name = f.get_name().to_str().unwrap();
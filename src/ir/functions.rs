@@ -48,6 +48,20 @@ impl<'llvm, 'm> Functions<'llvm, 'm> {
     // LLVM IR for constructor of [`Integer`](Type::Integer) type from C string
     add_builtin_function!(integer_from_c_string: (c_string) -> integer);
 
+    // LLVM IR for conversion of [`Integer`](Type::Integer) to i64
+    add_builtin_function!(integer_as_i64: (integer) -> i64);
+
+    // LLVM IR for panic on overflow of fixed-width integers.
+    // Takes description of operation, like "I8 + I8"
+    add_builtin_function!(arithmetic_overflow: (c_string) -> void);
+
+    // LLVM IR for panic on division of fixed-width integers by zero
+    add_builtin_function!(division_by_zero: () -> void);
+
+    // LLVM IR for assertion, that reports its location.
+    // Takes condition, reference to message and location, like "src/main.ppl:3:2"
+    add_builtin_function!(assert_at: (bool, pointer, c_string) -> void);
+
     // LLVM IR for constructor of `Rational` type from C string
     add_builtin_function!(rational_from_c_string: (c_string) -> rational);
 
@@ -0,0 +1,109 @@
+use inkwell::{context::Context, module::Module, AddressSpace, IntPredicate};
+
+/// Test, that can be run by test harness
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestCase {
+    /// Name of the test
+    pub name: String,
+    /// Mangled name of test function
+    pub function: String,
+    /// Function, that initializes global variables of test's module
+    pub initializer: String,
+    /// Location of the test in source code, like `src/main.ppl:3:1`
+    pub location: String,
+}
+
+/// Generate module with `main` function, that runs tests.
+///
+/// Without arguments, `main` prints names and locations of tests, separated by tab, one per line.
+/// With test name as the first argument, `main` runs this test only.
+/// Exit code `2` means, that there is no test with such name
+pub fn test_harness<'llvm>(llvm: &'llvm Context, tests: &[TestCase]) -> Module<'llvm> {
+    let module = llvm.create_module("test harness");
+    let builder = llvm.create_builder();
+
+    let i32 = llvm.i32_type();
+    let ptr = llvm.ptr_type(AddressSpace::default());
+    let void_fn = llvm.void_type().fn_type(&[], false);
+
+    let puts = module.add_function("puts", i32.fn_type(&[ptr.into()], false), None);
+    let strcmp = module.add_function(
+        "strcmp",
+        i32.fn_type(&[ptr.into(), ptr.into()], false),
+        None,
+    );
+
+    let main = module.add_function("main", i32.fn_type(&[i32.into(), ptr.into()], false), None);
+    let argc = main.get_nth_param(0).unwrap().into_int_value();
+    let argv = main.get_nth_param(1).unwrap().into_pointer_value();
+
+    let entry = llvm.append_basic_block(main, "entry");
+    let list = llvm.append_basic_block(main, "list");
+    let run = llvm.append_basic_block(main, "run");
+
+    builder.position_at_end(entry);
+    let has_name = builder
+        .build_int_compare(IntPredicate::SGT, argc, i32.const_int(1, false), "")
+        .unwrap();
+    builder
+        .build_conditional_branch(has_name, run, list)
+        .unwrap();
+
+    builder.position_at_end(list);
+    for test in tests {
+        let line = builder
+            .build_global_string_ptr(&format!("{}\t{}", test.name, test.location), "")
+            .unwrap();
+        builder
+            .build_call(puts, &[line.as_pointer_value().into()], "")
+            .unwrap();
+    }
+    builder.build_return(Some(&i32.const_zero())).unwrap();
+
+    builder.position_at_end(run);
+    let name = unsafe { builder.build_gep(ptr, argv, &[i32.const_int(1, false)], "") }.unwrap();
+    let name = builder.build_load(ptr, name, "name").unwrap();
+    for test in tests {
+        let matched = llvm.append_basic_block(main, "matched");
+        let next = llvm.append_basic_block(main, "next");
+
+        let test_name = builder.build_global_string_ptr(&test.name, "").unwrap();
+        let cmp = builder
+            .build_call(
+                strcmp,
+                &[name.into(), test_name.as_pointer_value().into()],
+                "",
+            )
+            .unwrap()
+            .try_as_basic_value()
+            .left()
+            .unwrap()
+            .into_int_value();
+        let is_matched = builder
+            .build_int_compare(IntPredicate::EQ, cmp, i32.const_zero(), "")
+            .unwrap();
+        builder
+            .build_conditional_branch(is_matched, matched, next)
+            .unwrap();
+
+        builder.position_at_end(matched);
+        for function in [&test.initializer, &test.function] {
+            let function = module
+                .get_function(function)
+                .unwrap_or_else(|| module.add_function(function, void_fn, None));
+            builder.build_call(function, &[], "").unwrap();
+        }
+        builder.build_return(Some(&i32.const_zero())).unwrap();
+
+        builder.position_at_end(next);
+    }
+    builder
+        .build_return(Some(&i32.const_int(2, false)))
+        .unwrap();
+
+    module
+        .verify()
+        .expect("Should never produce invalid test harness");
+
+    module
+}
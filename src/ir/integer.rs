@@ -0,0 +1,187 @@
+use inkwell::{
+    attributes::{Attribute, AttributeLoc},
+    intrinsics::Intrinsic,
+    module::{Linkage, Module},
+    types::BasicType,
+    values::{BasicMetadataValueEnum, BasicValue, FunctionValue, IntValue},
+    AddressSpace, IntPredicate,
+};
+
+/// Minimal integer, that runtime stores inline
+pub const SMALL_INTEGER_MIN: i64 = -(1 << 62);
+/// Maximal integer, that runtime stores inline
+pub const SMALL_INTEGER_MAX: i64 = (1 << 62) - 1;
+
+/// Tagged representation of small integer, as stored by runtime
+pub fn small_integer_bits(value: i64) -> Option<u64> {
+    (SMALL_INTEGER_MIN..=SMALL_INTEGER_MAX)
+        .contains(&value)
+        .then_some(((value << 1) | 1) as u64)
+}
+
+/// Operation on integers, that has fast path for small integers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operation {
+    Plus,
+    Minus,
+    Star,
+    Less,
+    Eq,
+}
+
+impl Operation {
+    /// Operation, implemented by runtime function with such name
+    fn implemented_by(name: &str) -> Option<Self> {
+        Some(match name {
+            "integer_plus_integer" => Self::Plus,
+            "integer_minus_integer" => Self::Minus,
+            "integer_star_integer" => Self::Star,
+            "integer_less_integer" => Self::Less,
+            "integer_eq_integer" => Self::Eq,
+            _ => return None,
+        })
+    }
+}
+
+/// Get function, that computes result for small integers inline
+/// and calls runtime `function` for big integers or on overflow.
+///
+/// Small integer `x` is stored by runtime as pointer with value `2x + 1`.
+/// Returns `None` if there is no fast path for `function`
+pub fn with_small_integer_fast_path<'llvm>(
+    module: &Module<'llvm>,
+    function: FunctionValue<'llvm>,
+) -> Option<FunctionValue<'llvm>> {
+    let name = function.get_name().to_str().ok()?;
+    let operation = Operation::implemented_by(name)?;
+
+    let fast_name = format!("{name}.small");
+    if let Some(fast) = module.get_function(&fast_name) {
+        return Some(fast);
+    }
+
+    let llvm = module.get_context();
+    let builder = llvm.create_builder();
+    let i64 = llvm.i64_type();
+    let one = i64.const_int(1, false);
+
+    let fast = module.add_function(&fast_name, function.get_type(), Some(Linkage::Private));
+    let inline = Attribute::get_named_enum_kind_id("alwaysinline");
+    fast.add_attribute(
+        AttributeLoc::Function,
+        llvm.create_enum_attribute(inline, 0),
+    );
+
+    let entry = llvm.append_basic_block(fast, "entry");
+    let small = llvm.append_basic_block(fast, "small");
+    let big = llvm.append_basic_block(fast, "big");
+
+    builder.position_at_end(entry);
+    let [x, y]: [IntValue; 2] = [0, 1].map(|i| {
+        let integer = fast.get_nth_param(i).unwrap().into_struct_value();
+        let data = builder
+            .build_extract_value(integer, 0, "")
+            .unwrap()
+            .into_pointer_value();
+        builder.build_ptr_to_int(data, i64, "").unwrap()
+    });
+    let tags = builder.build_and(x, y, "").unwrap();
+    let tag = builder.build_and(tags, one, "").unwrap();
+    let both_small = builder
+        .build_int_compare(IntPredicate::NE, tag, i64.const_zero(), "")
+        .unwrap();
+    builder
+        .build_conditional_branch(both_small, small, big)
+        .unwrap();
+
+    builder.position_at_end(big);
+    let args = fast
+        .get_param_iter()
+        .map(Into::into)
+        .collect::<Vec<BasicMetadataValueEnum>>();
+    let result = builder
+        .build_call(function, &args, "")
+        .unwrap()
+        .try_as_basic_value()
+        .left()
+        .unwrap();
+    builder.build_return(Some(&result)).unwrap();
+
+    builder.position_at_end(small);
+    let (intrinsic, lhs, rhs) = match operation {
+        // Order of tagged integers is the same as order of their values
+        Operation::Less | Operation::Eq => {
+            let predicate = match operation {
+                Operation::Less => IntPredicate::SLT,
+                _ => IntPredicate::EQ,
+            };
+            let result = builder.build_int_compare(predicate, x, y, "").unwrap();
+            builder.build_return(Some(&result)).unwrap();
+            return Some(fast);
+        }
+        // (2x + 1) + 2y = 2(x + y) + 1
+        Operation::Plus => (
+            "llvm.sadd.with.overflow",
+            x,
+            builder.build_int_sub(y, one, "").unwrap(),
+        ),
+        // (2x + 1) - 2y = 2(x - y) + 1
+        Operation::Minus => (
+            "llvm.ssub.with.overflow",
+            x,
+            builder.build_int_sub(y, one, "").unwrap(),
+        ),
+        // x * 2y + 1 = 2xy + 1
+        Operation::Star => (
+            "llvm.smul.with.overflow",
+            builder.build_right_shift(x, one, true, "").unwrap(),
+            builder.build_int_sub(y, one, "").unwrap(),
+        ),
+    };
+    let intrinsic = Intrinsic::find(intrinsic)
+        .and_then(|intrinsic| intrinsic.get_declaration(module, &[i64.as_basic_type_enum()]))
+        .expect("LLVM should have arithmetic with overflow intrinsics");
+    let result = builder
+        .build_call(intrinsic, &[lhs.into(), rhs.into()], "")
+        .unwrap()
+        .try_as_basic_value()
+        .left()
+        .unwrap()
+        .into_struct_value();
+    let value = builder
+        .build_extract_value(result, 0, "")
+        .unwrap()
+        .into_int_value();
+    let overflow = builder
+        .build_extract_value(result, 1, "")
+        .unwrap()
+        .into_int_value();
+
+    // Results, that don't fit into small integer, are computed by runtime
+    let fits = llvm.append_basic_block(fast, "fits");
+    builder
+        .build_conditional_branch(overflow, big, fits)
+        .unwrap();
+
+    builder.position_at_end(fits);
+    let value = match operation {
+        Operation::Star => builder.build_or(value, one, "").unwrap(),
+        _ => value,
+    };
+    let data = builder
+        .build_int_to_ptr(value, llvm.ptr_type(AddressSpace::default()), "")
+        .unwrap();
+    let integer = function
+        .get_type()
+        .get_return_type()
+        .expect("Integer operations return value")
+        .into_struct_type();
+    let integer = builder
+        .build_insert_value(integer.get_undef(), data, 0, "")
+        .unwrap();
+    builder
+        .build_return(Some(&integer.as_basic_value_enum()))
+        .unwrap();
+
+    Some(fast)
+}
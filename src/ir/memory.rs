@@ -0,0 +1,211 @@
+use inkwell::{
+    attributes::{Attribute, AttributeLoc},
+    builder::Builder,
+    module::{Linkage, Module},
+    types::BasicTypeEnum,
+    values::{BasicValue, BasicValueEnum, FunctionValue, IntValue},
+    AddressSpace, IntPredicate,
+};
+
+use super::Functions;
+
+/// Runtime functions for memory, that are implemented by compiler
+const MEMORY_INTRINSICS: [&str; 4] = [
+    "address_of",
+    "read_memory",
+    "read_pointer",
+    "memory_address_plus_integer",
+];
+
+/// Runtime functions for `Pointer<T>`, that are implemented by compiler for each `T`
+pub const POINTER_INTRINSICS: [&str; 2] = ["pointer_plus_integer", "write_to_pointer"];
+
+/// Define body of `function`, if it's a compiler intrinsic for memory.
+///
+/// `MemoryAddress` is a wrapper around pointer, so intrinsics don't need runtime calls.
+/// Every module, that uses intrinsic, gets its own private definition of it
+pub fn define_memory_intrinsic<'llvm>(module: &Module<'llvm>, function: FunctionValue<'llvm>) {
+    let Ok(name) = function.get_name().to_str() else {
+        return;
+    };
+    if function.count_basic_blocks() > 0 || !MEMORY_INTRINSICS.contains(&name) {
+        return;
+    }
+
+    let llvm = module.get_context();
+    let ptr = llvm.ptr_type(AddressSpace::default());
+    define_intrinsic(module, function, |builder| match name {
+        // fn<T> address of <ref: &T> -> MemoryAddress
+        "address_of" => {
+            let reference = function.get_first_param().unwrap();
+            let address = wrap(builder, function.get_type().get_return_type(), reference);
+            builder.build_return(Some(&address)).unwrap();
+        }
+        // fn<T> <ty: Type<T>> at <address: &MemoryAddress> -> ReferenceMut<T>
+        // fn<T> value at <ptr: &Pointer<T>> -> ReferenceMut<T>
+        "read_memory" | "read_pointer" => {
+            let address = function.get_last_param().unwrap().into_pointer_value();
+            let reference = builder.build_load(ptr, address, "").unwrap();
+            builder.build_return(Some(&reference)).unwrap();
+        }
+        // fn <address: MemoryAddress> + <offset: Integer> -> MemoryAddress
+        "memory_address_plus_integer" => {
+            let address = function.get_first_param().unwrap().into_struct_value();
+            let pointer = builder
+                .build_extract_value(address, 0, "")
+                .unwrap()
+                .into_pointer_value();
+
+            let offset = build_offset(module, function, builder);
+            let pointer =
+                unsafe { builder.build_gep(llvm.i8_type(), pointer, &[offset], "") }.unwrap();
+            let address = wrap(
+                builder,
+                function.get_type().get_return_type(),
+                pointer.into(),
+            );
+            builder.build_return(Some(&address)).unwrap();
+        }
+        _ => unreachable!("`{name}` is not a memory intrinsic"),
+    });
+}
+
+/// Define body of `function`, if it's not defined yet,
+/// as `intrinsic` for `Pointer<T>` with values of `element` type
+pub fn define_pointer_intrinsic<'llvm>(
+    module: &Module<'llvm>,
+    function: FunctionValue<'llvm>,
+    intrinsic: &str,
+    element: BasicTypeEnum<'llvm>,
+) {
+    if function.count_basic_blocks() > 0 {
+        return;
+    }
+
+    define_intrinsic(module, function, |builder| match intrinsic {
+        // fn<T> <ptr: Pointer<T>> + <i: Integer> -> Pointer<T>
+        "pointer_plus_integer" => {
+            let pointer = function.get_first_param().unwrap().into_struct_value();
+            let address = builder
+                .build_extract_value(pointer, 0, "")
+                .unwrap()
+                .into_struct_value();
+            let data = builder
+                .build_extract_value(address, 0, "")
+                .unwrap()
+                .into_pointer_value();
+
+            let offset = build_offset(module, function, builder);
+            let data = unsafe { builder.build_gep(element, data, &[offset], "") }.unwrap();
+            let address = wrap(builder, Some(address.get_type().into()), data.into());
+            let pointer = wrap(builder, function.get_type().get_return_type(), address);
+            builder.build_return(Some(&pointer)).unwrap();
+        }
+        // fn<T> write <x: &T> to <ptr: &Pointer<T>>
+        "write_to_pointer" => {
+            let ptr = module.get_context().ptr_type(AddressSpace::default());
+            let value = function.get_first_param().unwrap().into_pointer_value();
+            let pointer = function.get_last_param().unwrap().into_pointer_value();
+            let data = builder
+                .build_load(ptr, pointer, "")
+                .unwrap()
+                .into_pointer_value();
+            let value = builder.build_load(element, value, "").unwrap();
+            builder.build_store(data, value).unwrap();
+            builder.build_return(None).unwrap();
+        }
+        _ => unreachable!("`{intrinsic}` is not a pointer intrinsic"),
+    });
+}
+
+/// Emit body of intrinsic `function` and make it private and always inlined
+fn define_intrinsic<'llvm>(
+    module: &Module<'llvm>,
+    function: FunctionValue<'llvm>,
+    body: impl FnOnce(&Builder<'llvm>),
+) {
+    let llvm = module.get_context();
+    let builder = llvm.create_builder();
+
+    let entry = llvm.append_basic_block(function, "entry");
+    builder.position_at_end(entry);
+    body(&builder);
+
+    function.set_linkage(Linkage::Private);
+    let inline = Attribute::get_named_enum_kind_id("alwaysinline");
+    function.add_attribute(
+        AttributeLoc::Function,
+        llvm.create_enum_attribute(inline, 0),
+    );
+}
+
+/// Wrap `value` into the first field of `ty` struct
+fn wrap<'llvm>(
+    builder: &Builder<'llvm>,
+    ty: Option<BasicTypeEnum<'llvm>>,
+    value: BasicValueEnum<'llvm>,
+) -> BasicValueEnum<'llvm> {
+    let ty = ty
+        .expect("Intrinsic should return wrapped pointer")
+        .into_struct_type();
+    builder
+        .build_insert_value(ty.get_undef(), value, 0, "")
+        .unwrap()
+        .as_basic_value_enum()
+}
+
+/// Convert last parameter of `function`, that is an `Integer` offset, to `i64`.
+/// Small integers are converted inline, big ones are converted by runtime
+fn build_offset<'llvm>(
+    module: &Module<'llvm>,
+    function: FunctionValue<'llvm>,
+    builder: &Builder<'llvm>,
+) -> IntValue<'llvm> {
+    let llvm = module.get_context();
+    let i64 = llvm.i64_type();
+
+    let offset = function.get_last_param().unwrap();
+    let bits = builder
+        .build_extract_value(offset.into_struct_value(), 0, "")
+        .unwrap()
+        .into_pointer_value();
+    let bits = builder.build_ptr_to_int(bits, i64, "").unwrap();
+    let tag = builder
+        .build_and(bits, i64.const_int(1, false), "")
+        .unwrap();
+    let is_small = builder
+        .build_int_compare(IntPredicate::NE, tag, i64.const_zero(), "")
+        .unwrap();
+
+    let small = llvm.append_basic_block(function, "small");
+    let big = llvm.append_basic_block(function, "big");
+    let add = llvm.append_basic_block(function, "add");
+    builder
+        .build_conditional_branch(is_small, small, big)
+        .unwrap();
+
+    builder.position_at_end(small);
+    let small_offset = builder
+        .build_right_shift(bits, i64.const_int(1, false), true, "")
+        .unwrap();
+    builder.build_unconditional_branch(add).unwrap();
+
+    builder.position_at_end(big);
+    let big_offset = builder
+        .build_call(
+            Functions::new(module).integer_as_i64(),
+            &[offset.into()],
+            "",
+        )
+        .unwrap()
+        .try_as_basic_value()
+        .left()
+        .unwrap()
+        .into_int_value();
+    builder.build_unconditional_branch(add).unwrap();
+
+    builder.position_at_end(add);
+    let offset = builder.build_phi(i64, "offset").unwrap();
+    offset.add_incoming(&[(&small_offset, small), (&big_offset, big)]);
+    offset.as_basic_value().into_int_value()
+}
@@ -10,7 +10,22 @@ pub use types::*;
 mod functions;
 pub use functions::*;
 
+mod integer;
+pub use integer::*;
+
+mod memory;
+pub use memory::*;
+
+mod numeric;
+pub use numeric::*;
+
 mod context;
 pub use context::*;
 
 pub(crate) mod inkwell;
+
+mod target;
+pub use target::*;
+
+mod harness;
+pub use harness::*;
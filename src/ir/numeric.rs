@@ -0,0 +1,749 @@
+use inkwell::{
+    attributes::{Attribute, AttributeLoc},
+    builder::Builder,
+    intrinsics::Intrinsic,
+    module::{Linkage, Module},
+    types::BasicTypeEnum,
+    values::{BasicMetadataValueEnum, BasicValueEnum, FunctionValue, IntValue},
+    FloatPredicate, IntPredicate,
+};
+
+use crate::hir::{BuiltinClass, FixedWidth};
+
+use super::{Functions, Types};
+
+/// Unary operation on fixed-width numbers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum UnaryOperation {
+    /// `-x`, that panics on overflow
+    Minus,
+    /// Bitwise `not x`
+    Not,
+    Sqrt,
+    Exp,
+    Log,
+    Log2,
+    Log10,
+    Sin,
+    Cos,
+    Floor,
+    Ceil,
+    Round,
+    Trunc,
+    Abs,
+    IsNan,
+    IsInfinite,
+    IsFinite,
+}
+
+impl UnaryOperation {
+    /// Get operation by its part of mangled name
+    fn from_mangled(name: &str) -> Option<Self> {
+        use UnaryOperation::*;
+        Some(match name {
+            "minus" => Minus,
+            "not" => Not,
+            "sqrt" => Sqrt,
+            "exp" => Exp,
+            "log" => Log,
+            "log2" => Log2,
+            "log10" => Log10,
+            "sin" => Sin,
+            "cos" => Cos,
+            "floor" => Floor,
+            "ceil" => Ceil,
+            "round" => Round,
+            "trunc" => Trunc,
+            "abs" => Abs,
+            "is_nan" => IsNan,
+            "is_infinite" => IsInfinite,
+            "is_finite" => IsFinite,
+            _ => return None,
+        })
+    }
+
+    /// Is this operation defined for numbers of this type?
+    fn is_defined_for(&self, number: FixedWidth) -> bool {
+        match (self, number) {
+            (UnaryOperation::Minus, FixedWidth::Unsigned(_)) => false,
+            (UnaryOperation::Minus, _) => true,
+            (UnaryOperation::Not, number) => number.is_integer(),
+            (_, number) => number.is_float(),
+        }
+    }
+
+    /// Name of LLVM intrinsic, that implements this operation for floats
+    fn llvm_intrinsic(&self) -> Option<&'static str> {
+        use UnaryOperation::*;
+        Some(match self {
+            Sqrt => "llvm.sqrt",
+            Exp => "llvm.exp",
+            Log => "llvm.log",
+            Log2 => "llvm.log2",
+            Log10 => "llvm.log10",
+            Sin => "llvm.sin",
+            Cos => "llvm.cos",
+            Floor => "llvm.floor",
+            Ceil => "llvm.ceil",
+            Round => "llvm.round",
+            Trunc => "llvm.trunc",
+            Abs => "llvm.fabs",
+            _ => return None,
+        })
+    }
+}
+
+/// Binary operation on fixed-width numbers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BinaryOperation {
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Mod,
+    WrappingPlus,
+    WrappingMinus,
+    WrappingStar,
+    SaturatingPlus,
+    SaturatingMinus,
+    SaturatingStar,
+    Eq,
+    Less,
+    And,
+    Or,
+    Xor,
+    Shl,
+    Shr,
+    Min,
+    Max,
+    Power,
+}
+
+impl BinaryOperation {
+    /// Get operation by its part of mangled name
+    fn from_mangled(name: &str) -> Option<Self> {
+        use BinaryOperation::*;
+        Some(match name {
+            "plus" => Plus,
+            "minus" => Minus,
+            "star" => Star,
+            "slash" => Slash,
+            "mod" => Mod,
+            "wrapping_plus" => WrappingPlus,
+            "wrapping_minus" => WrappingMinus,
+            "wrapping_star" => WrappingStar,
+            "saturating_plus" => SaturatingPlus,
+            "saturating_minus" => SaturatingMinus,
+            "saturating_star" => SaturatingStar,
+            "eq" => Eq,
+            "less" => Less,
+            "and" => And,
+            "or" => Or,
+            "xor" => Xor,
+            "shl" => Shl,
+            "shr" => Shr,
+            "min" => Min,
+            "max" => Max,
+            "power" => Power,
+            _ => return None,
+        })
+    }
+
+    /// Symbol of operation in PPL
+    fn symbol(&self) -> &'static str {
+        use BinaryOperation::*;
+        match self {
+            Plus => "+",
+            Minus => "-",
+            Star => "*",
+            Slash => "/",
+            Mod => "%",
+            WrappingPlus => "+%",
+            WrappingMinus => "-%",
+            WrappingStar => "*%",
+            SaturatingPlus => "+|",
+            SaturatingMinus => "-|",
+            SaturatingStar => "*|",
+            Eq => "==",
+            Less => "<",
+            And => "and",
+            Or => "or",
+            Xor => "xor",
+            Shl => "<<",
+            Shr => ">>",
+            Min => "min",
+            Max => "max",
+            Power => "^",
+        }
+    }
+
+    /// Is this operation defined for numbers of this type?
+    fn is_defined_for(&self, number: FixedWidth) -> bool {
+        use BinaryOperation::*;
+        match self {
+            Plus | Minus | Star | Slash | Mod | Eq | Less => true,
+            Min | Max | Power => number.is_float(),
+            _ => number.is_integer(),
+        }
+    }
+
+    /// Can this operation panic for numbers of this type?
+    fn may_fail_for(&self, number: FixedWidth) -> bool {
+        use BinaryOperation::*;
+        number.is_integer() && matches!(self, Plus | Minus | Star | Slash | Mod | Shl | Shr)
+    }
+}
+
+/// Operation on fixed-width numbers, that is implemented by compiler
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NumericIntrinsic {
+    /// Unary operation on number of given type
+    Unary(UnaryOperation, FixedWidth),
+    /// Binary operation on numbers of given type
+    Binary(BinaryOperation, FixedWidth),
+    /// Check, that binary operation on numbers of given type would panic
+    Fails(BinaryOperation, FixedWidth),
+    /// Conversion of number from one type to another
+    Cast { from: FixedWidth, to: FixedWidth },
+}
+
+/// Get fixed-width number type by its part of mangled name, like `i8`
+fn fixed_width(name: &str) -> Option<FixedWidth> {
+    name.to_uppercase()
+        .parse::<BuiltinClass>()
+        .ok()?
+        .fixed_width()
+}
+
+impl NumericIntrinsic {
+    /// Get intrinsic by mangled name of function.
+    ///
+    /// Names of intrinsics are:
+    /// - `{operation}_{t}` for unary operations, like `minus_i8` or `sqrt_f64`
+    /// - `{t}_{operation}_{t}` for binary operations
+    /// - `{t}_{operation}_fails_{t}` for checks, that binary operation would panic
+    /// - `{from}_as_{to}` for conversions
+    fn from_mangled(name: &str) -> Option<Self> {
+        if let Some((operation, ty)) = name.rsplit_once('_') {
+            let operation = UnaryOperation::from_mangled(operation);
+            if let (Some(operation), Some(number)) = (operation, fixed_width(ty)) {
+                return operation
+                    .is_defined_for(number)
+                    .then_some(Self::Unary(operation, number));
+            }
+        }
+
+        let (lhs, rest) = name.split_once('_')?;
+        let (operation, rhs) = rest.rsplit_once('_')?;
+        let (lhs, rhs) = (fixed_width(lhs)?, fixed_width(rhs)?);
+        if operation == "as" {
+            return (lhs != rhs).then_some(Self::Cast { from: lhs, to: rhs });
+        }
+
+        if let Some(operation) = operation.strip_suffix("_fails") {
+            let operation = BinaryOperation::from_mangled(operation)?;
+            return (lhs == rhs && operation.may_fail_for(lhs))
+                .then_some(Self::Fails(operation, lhs));
+        }
+
+        let operation = BinaryOperation::from_mangled(operation)?;
+        if lhs != rhs || !operation.is_defined_for(lhs) {
+            return None;
+        }
+        Some(Self::Binary(operation, lhs))
+    }
+}
+
+/// Helper to build body of intrinsic
+struct Body<'llvm, 'm> {
+    /// Module with intrinsic
+    module: &'m Module<'llvm>,
+    /// Intrinsic function itself
+    function: FunctionValue<'llvm>,
+    /// Builder, positioned inside intrinsic
+    builder: Builder<'llvm>,
+}
+
+impl<'llvm> Body<'llvm, '_> {
+    /// Call LLVM intrinsic, overloaded for `types`
+    fn call_llvm_intrinsic(
+        &self,
+        name: &str,
+        types: &[BasicTypeEnum<'llvm>],
+        args: &[BasicMetadataValueEnum<'llvm>],
+    ) -> BasicValueEnum<'llvm> {
+        let intrinsic = Intrinsic::find(name)
+            .and_then(|intrinsic| intrinsic.get_declaration(self.module, types))
+            .unwrap_or_else(|| panic!("LLVM should have `{name}` intrinsic"));
+        self.builder
+            .build_call(intrinsic, args, "")
+            .unwrap()
+            .try_as_basic_value()
+            .left()
+            .unwrap()
+    }
+
+    /// Call runtime function, that never returns, if `condition` is true
+    fn panic_if(
+        &self,
+        condition: IntValue<'llvm>,
+        panic: FunctionValue<'llvm>,
+        message: Option<&str>,
+    ) {
+        let llvm = self.module.get_context();
+        let fail = llvm.append_basic_block(self.function, "panic");
+        let ok = llvm.append_basic_block(self.function, "ok");
+        self.builder
+            .build_conditional_branch(condition, fail, ok)
+            .unwrap();
+
+        self.builder.position_at_end(fail);
+        let args: Vec<BasicMetadataValueEnum> = message
+            .map(|message| {
+                let message = self.builder.build_global_string_ptr(message, "").unwrap();
+                vec![message.as_pointer_value().into()]
+            })
+            .unwrap_or_default();
+        let noreturn = Attribute::get_named_enum_kind_id("noreturn");
+        panic.add_attribute(
+            AttributeLoc::Function,
+            llvm.create_enum_attribute(noreturn, 0),
+        );
+        self.builder.build_call(panic, &args, "").unwrap();
+        self.builder.build_unreachable().unwrap();
+
+        self.builder.position_at_end(ok);
+    }
+
+    /// Panic with message about overflow of `operation`, if `overflow` is true
+    fn panic_on_overflow(&self, overflow: IntValue<'llvm>, operation: &str) {
+        let panic = Functions::new(self.module).arithmetic_overflow();
+        self.panic_if(overflow, panic, Some(operation));
+    }
+
+    /// Call arithmetic intrinsic like `llvm.sadd.with.overflow`.
+    /// Returns result and overflow flag
+    fn with_overflow(
+        &self,
+        intrinsic: &str,
+        x: IntValue<'llvm>,
+        y: IntValue<'llvm>,
+    ) -> (IntValue<'llvm>, IntValue<'llvm>) {
+        let result = self
+            .call_llvm_intrinsic(intrinsic, &[x.get_type().into()], &[x.into(), y.into()])
+            .into_struct_value();
+        let [value, overflow] = [0, 1].map(|i| {
+            self.builder
+                .build_extract_value(result, i, "")
+                .unwrap()
+                .into_int_value()
+        });
+        (value, overflow)
+    }
+
+    /// Call arithmetic intrinsic like `llvm.sadd.with.overflow`
+    /// and panic on overflow
+    fn checked(
+        &self,
+        intrinsic: &str,
+        x: IntValue<'llvm>,
+        y: IntValue<'llvm>,
+        operation: &str,
+    ) -> IntValue<'llvm> {
+        let (value, overflow) = self.with_overflow(intrinsic, x, y);
+        self.panic_on_overflow(overflow, operation);
+        value
+    }
+
+    /// Build unary operation on number
+    fn unary(&self, operation: UnaryOperation, number: FixedWidth) -> BasicValueEnum<'llvm> {
+        let x = self.function.get_first_param().unwrap();
+        match (operation, number) {
+            (UnaryOperation::Minus, FixedWidth::Float(_)) => self
+                .builder
+                .build_float_neg(x.into_float_value(), "")
+                .unwrap()
+                .into(),
+            (UnaryOperation::Minus, _) => {
+                let x = x.into_int_value();
+                let zero = x.get_type().const_zero();
+                self.checked("llvm.ssub.with.overflow", zero, x, &format!("-{number}"))
+                    .into()
+            }
+            (UnaryOperation::Not, _) => self
+                .builder
+                .build_not(x.into_int_value(), "")
+                .unwrap()
+                .into(),
+            (UnaryOperation::IsNan, _) => {
+                let x = x.into_float_value();
+                self.builder
+                    .build_float_compare(FloatPredicate::UNO, x, x, "")
+                    .unwrap()
+                    .into()
+            }
+            (UnaryOperation::IsInfinite | UnaryOperation::IsFinite, _) => {
+                let abs = self
+                    .call_llvm_intrinsic("llvm.fabs", &[x.get_type()], &[x.into()])
+                    .into_float_value();
+                let infinity = abs.get_type().const_float(f64::INFINITY);
+                // Comparisons with NaN are false, so it's neither infinite nor finite
+                let predicate = match operation {
+                    UnaryOperation::IsInfinite => FloatPredicate::OEQ,
+                    _ => FloatPredicate::OLT,
+                };
+                self.builder
+                    .build_float_compare(predicate, abs, infinity, "")
+                    .unwrap()
+                    .into()
+            }
+            (operation, _) => {
+                let intrinsic = operation
+                    .llvm_intrinsic()
+                    .expect("Math functions should be implemented by LLVM intrinsics");
+                self.call_llvm_intrinsic(intrinsic, &[x.get_type()], &[x.into()])
+            }
+        }
+    }
+
+    /// Build binary operation on floats
+    fn float_binary(&self, operation: BinaryOperation) -> BasicValueEnum<'llvm> {
+        let x = self.function.get_first_param().unwrap().into_float_value();
+        let y = self.function.get_last_param().unwrap().into_float_value();
+        let builder = &self.builder;
+        match operation {
+            BinaryOperation::Plus => builder.build_float_add(x, y, "").unwrap().into(),
+            BinaryOperation::Minus => builder.build_float_sub(x, y, "").unwrap().into(),
+            BinaryOperation::Star => builder.build_float_mul(x, y, "").unwrap().into(),
+            BinaryOperation::Slash => builder.build_float_div(x, y, "").unwrap().into(),
+            BinaryOperation::Mod => builder.build_float_rem(x, y, "").unwrap().into(),
+            BinaryOperation::Eq => builder
+                .build_float_compare(FloatPredicate::OEQ, x, y, "")
+                .unwrap()
+                .into(),
+            BinaryOperation::Less => builder
+                .build_float_compare(FloatPredicate::OLT, x, y, "")
+                .unwrap()
+                .into(),
+            // `min` and `max` return the other operand, if one of them is NaN
+            BinaryOperation::Min | BinaryOperation::Max | BinaryOperation::Power => {
+                let intrinsic = match operation {
+                    BinaryOperation::Min => "llvm.minnum",
+                    BinaryOperation::Max => "llvm.maxnum",
+                    _ => "llvm.pow",
+                };
+                self.call_llvm_intrinsic(intrinsic, &[x.get_type().into()], &[x.into(), y.into()])
+            }
+            _ => unreachable!("`{}` is not defined for floats", operation.symbol()),
+        }
+    }
+
+    /// Build binary operation on integers
+    fn int_binary(&self, operation: BinaryOperation, number: FixedWidth) -> BasicValueEnum<'llvm> {
+        use BinaryOperation::*;
+
+        let x = self.function.get_first_param().unwrap().into_int_value();
+        let y = self.function.get_last_param().unwrap().into_int_value();
+        let ty = x.get_type();
+        let bits = number.bits();
+        let signed = matches!(number, FixedWidth::Signed(_));
+        let sign = if signed { "s" } else { "u" };
+        let min = ty.const_int(1 << (bits - 1), false);
+        let max = ty.const_int(u64::MAX >> (64 - bits + signed as u32), false);
+        let description = format!("{number} {} {number}", operation.symbol());
+
+        let builder = &self.builder;
+        match operation {
+            Plus | Minus | Star => {
+                let intrinsic = match operation {
+                    Plus => "add",
+                    Minus => "sub",
+                    _ => "mul",
+                };
+                let intrinsic = format!("llvm.{sign}{intrinsic}.with.overflow");
+                self.checked(&intrinsic, x, y, &description).into()
+            }
+            Slash | Mod => {
+                let is_zero = builder
+                    .build_int_compare(IntPredicate::EQ, y, ty.const_zero(), "")
+                    .unwrap();
+                let division_by_zero = Functions::new(self.module).division_by_zero();
+                self.panic_if(is_zero, division_by_zero, None);
+
+                if signed {
+                    // `MIN / -1` doesn't fit into type
+                    let is_min = builder
+                        .build_int_compare(IntPredicate::EQ, x, min, "")
+                        .unwrap();
+                    let is_minus_one = builder
+                        .build_int_compare(IntPredicate::EQ, y, ty.const_all_ones(), "")
+                        .unwrap();
+                    let overflow = builder.build_and(is_min, is_minus_one, "").unwrap();
+                    self.panic_on_overflow(overflow, &description);
+                }
+
+                match (operation, signed) {
+                    (Slash, true) => builder.build_int_signed_div(x, y, ""),
+                    (Slash, false) => builder.build_int_unsigned_div(x, y, ""),
+                    (_, true) => builder.build_int_signed_rem(x, y, ""),
+                    (_, false) => builder.build_int_unsigned_rem(x, y, ""),
+                }
+                .unwrap()
+                .into()
+            }
+            WrappingPlus => builder.build_int_add(x, y, "").unwrap().into(),
+            WrappingMinus => builder.build_int_sub(x, y, "").unwrap().into(),
+            WrappingStar => builder.build_int_mul(x, y, "").unwrap().into(),
+            SaturatingPlus | SaturatingMinus => {
+                let intrinsic = match operation {
+                    SaturatingPlus => "add",
+                    _ => "sub",
+                };
+                let intrinsic = format!("llvm.{sign}{intrinsic}.sat");
+                self.call_llvm_intrinsic(&intrinsic, &[ty.into()], &[x.into(), y.into()])
+            }
+            SaturatingStar => {
+                let intrinsic = format!("llvm.{sign}mul.with.overflow");
+                let (value, overflow) = self.with_overflow(&intrinsic, x, y);
+                let limit = if signed {
+                    // Product is negative, if signs of operands differ
+                    let signs = builder.build_xor(x, y, "").unwrap();
+                    let negative = builder
+                        .build_int_compare(IntPredicate::SLT, signs, ty.const_zero(), "")
+                        .unwrap();
+                    builder
+                        .build_select(negative, min, max, "")
+                        .unwrap()
+                        .into_int_value()
+                } else {
+                    max
+                };
+                builder.build_select(overflow, limit, value, "").unwrap()
+            }
+            Eq => builder
+                .build_int_compare(IntPredicate::EQ, x, y, "")
+                .unwrap()
+                .into(),
+            Less => {
+                let predicate = if signed {
+                    IntPredicate::SLT
+                } else {
+                    IntPredicate::ULT
+                };
+                builder
+                    .build_int_compare(predicate, x, y, "")
+                    .unwrap()
+                    .into()
+            }
+            And => builder.build_and(x, y, "").unwrap().into(),
+            Or => builder.build_or(x, y, "").unwrap().into(),
+            Xor => builder.build_xor(x, y, "").unwrap().into(),
+            Min | Max | Power => {
+                unreachable!("`{}` is not defined for integers", operation.symbol())
+            }
+            Shl | Shr => {
+                // LLVM doesn't define shifts by number of bits or more.
+                // Negative amount is out of range too, when compared as unsigned
+                let out_of_range = builder
+                    .build_int_compare(IntPredicate::UGE, y, ty.const_int(bits as u64, false), "")
+                    .unwrap();
+                self.panic_on_overflow(out_of_range, &description);
+                match operation {
+                    Shl => builder.build_left_shift(x, y, ""),
+                    _ => builder.build_right_shift(x, y, signed, ""),
+                }
+                .unwrap()
+                .into()
+            }
+        }
+    }
+
+    /// Build check, that binary operation on integers would panic
+    fn fails(&self, operation: BinaryOperation, number: FixedWidth) -> BasicValueEnum<'llvm> {
+        use BinaryOperation::*;
+
+        let x = self.function.get_first_param().unwrap().into_int_value();
+        let y = self.function.get_last_param().unwrap().into_int_value();
+        let ty = x.get_type();
+        let bits = number.bits();
+        let signed = matches!(number, FixedWidth::Signed(_));
+        let sign = if signed { "s" } else { "u" };
+
+        let builder = &self.builder;
+        match operation {
+            Plus | Minus | Star => {
+                let intrinsic = match operation {
+                    Plus => "add",
+                    Minus => "sub",
+                    _ => "mul",
+                };
+                let intrinsic = format!("llvm.{sign}{intrinsic}.with.overflow");
+                self.with_overflow(&intrinsic, x, y).1.into()
+            }
+            Slash | Mod => {
+                let is_zero = builder
+                    .build_int_compare(IntPredicate::EQ, y, ty.const_zero(), "")
+                    .unwrap();
+                if !signed {
+                    return is_zero.into();
+                }
+
+                // `MIN / -1` doesn't fit into type
+                let min = ty.const_int(1 << (bits - 1), false);
+                let is_min = builder
+                    .build_int_compare(IntPredicate::EQ, x, min, "")
+                    .unwrap();
+                let is_minus_one = builder
+                    .build_int_compare(IntPredicate::EQ, y, ty.const_all_ones(), "")
+                    .unwrap();
+                let overflow = builder.build_and(is_min, is_minus_one, "").unwrap();
+                builder.build_or(is_zero, overflow, "").unwrap().into()
+            }
+            Shl | Shr => builder
+                .build_int_compare(IntPredicate::UGE, y, ty.const_int(bits as u64, false), "")
+                .unwrap()
+                .into(),
+            _ => unreachable!("`{}` never panics", operation.symbol()),
+        }
+    }
+
+    /// Build conversion of number from one type to another
+    fn cast(&self, from: FixedWidth, to: FixedWidth) -> BasicValueEnum<'llvm> {
+        let x = self.function.get_first_param().unwrap();
+        let target = Types::new(self.module.get_context()).fixed_width(to);
+        let builder = &self.builder;
+        match (from, to) {
+            (FixedWidth::Float(_), FixedWidth::Float(_)) => builder
+                .build_float_cast(x.into_float_value(), target.into_float_type(), "")
+                .unwrap()
+                .into(),
+            // Out of range floats are saturated and NaN becomes 0
+            (FixedWidth::Float(_), _) => {
+                let intrinsic = match to {
+                    FixedWidth::Signed(_) => "llvm.fptosi.sat",
+                    _ => "llvm.fptoui.sat",
+                };
+                self.call_llvm_intrinsic(intrinsic, &[target, x.get_type()], &[x.into()])
+            }
+            (FixedWidth::Signed(_), FixedWidth::Float(_)) => builder
+                .build_signed_int_to_float(x.into_int_value(), target.into_float_type(), "")
+                .unwrap()
+                .into(),
+            (FixedWidth::Unsigned(_), FixedWidth::Float(_)) => builder
+                .build_unsigned_int_to_float(x.into_int_value(), target.into_float_type(), "")
+                .unwrap()
+                .into(),
+            // Integers are truncated or extended, like in C
+            (_, _) => builder
+                .build_int_cast_sign_flag(
+                    x.into_int_value(),
+                    target.into_int_type(),
+                    matches!(from, FixedWidth::Signed(_)),
+                    "",
+                )
+                .unwrap()
+                .into(),
+        }
+    }
+}
+
+/// Define body of `function`, if it's a compiler intrinsic for fixed-width numbers.
+///
+/// Arithmetic, comparisons, bitwise operations and conversions of fixed-width numbers
+/// are lowered directly to LLVM instructions.
+/// Math functions on floats are lowered to LLVM intrinsics.
+/// Every module, that uses intrinsic, gets its own private definition of it
+pub fn define_numeric_intrinsic<'llvm>(module: &Module<'llvm>, function: FunctionValue<'llvm>) {
+    let Ok(name) = function.get_name().to_str() else {
+        return;
+    };
+    if function.count_basic_blocks() > 0 {
+        return;
+    }
+    let Some(intrinsic) = NumericIntrinsic::from_mangled(name) else {
+        return;
+    };
+
+    let llvm = module.get_context();
+    let body = Body {
+        module,
+        function,
+        builder: llvm.create_builder(),
+    };
+    let entry = llvm.append_basic_block(function, "entry");
+    body.builder.position_at_end(entry);
+
+    let result = match intrinsic {
+        NumericIntrinsic::Unary(operation, number) => body.unary(operation, number),
+        NumericIntrinsic::Binary(operation, number) if number.is_float() => {
+            body.float_binary(operation)
+        }
+        NumericIntrinsic::Binary(operation, number) => body.int_binary(operation, number),
+        NumericIntrinsic::Fails(operation, number) => body.fails(operation, number),
+        NumericIntrinsic::Cast { from, to } => body.cast(from, to),
+    };
+    body.builder.build_return(Some(&result)).unwrap();
+
+    function.set_linkage(Linkage::Private);
+    let inline = Attribute::get_named_enum_kind_id("alwaysinline");
+    function.add_attribute(
+        AttributeLoc::Function,
+        llvm.create_enum_attribute(inline, 0),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mangled_names() {
+        use FixedWidth::*;
+
+        assert_eq!(
+            NumericIntrinsic::from_mangled("i8_plus_i8"),
+            Some(NumericIntrinsic::Binary(BinaryOperation::Plus, Signed(8)))
+        );
+        assert_eq!(
+            NumericIntrinsic::from_mangled("u64_saturating_star_u64"),
+            Some(NumericIntrinsic::Binary(
+                BinaryOperation::SaturatingStar,
+                Unsigned(64)
+            ))
+        );
+        assert_eq!(
+            NumericIntrinsic::from_mangled("minus_f32"),
+            Some(NumericIntrinsic::Unary(UnaryOperation::Minus, Float(32)))
+        );
+        assert_eq!(
+            NumericIntrinsic::from_mangled("i16_as_f64"),
+            Some(NumericIntrinsic::Cast {
+                from: Signed(16),
+                to: Float(64)
+            })
+        );
+
+        assert_eq!(
+            NumericIntrinsic::from_mangled("is_nan_f64"),
+            Some(NumericIntrinsic::Unary(UnaryOperation::IsNan, Float(64)))
+        );
+        assert_eq!(
+            NumericIntrinsic::from_mangled("f64_max_f64"),
+            Some(NumericIntrinsic::Binary(BinaryOperation::Max, Float(64)))
+        );
+        assert_eq!(
+            NumericIntrinsic::from_mangled("u16_shl_fails_u16"),
+            Some(NumericIntrinsic::Fails(BinaryOperation::Shl, Unsigned(16)))
+        );
+
+        assert_eq!(NumericIntrinsic::from_mangled("minus_u8"), None);
+        assert_eq!(NumericIntrinsic::from_mangled("sqrt_i32"), None);
+        assert_eq!(NumericIntrinsic::from_mangled("sqrt_integer"), None);
+        assert_eq!(NumericIntrinsic::from_mangled("f64_xor_f64"), None);
+        assert_eq!(NumericIntrinsic::from_mangled("integer_plus_integer"), None);
+        assert_eq!(NumericIntrinsic::from_mangled("i32_as_string"), None);
+        assert_eq!(NumericIntrinsic::from_mangled("f64_plus_fails_f64"), None);
+        assert_eq!(NumericIntrinsic::from_mangled("i8_xor_fails_i8"), None);
+        assert_eq!(NumericIntrinsic::from_mangled("i8_plus_fails_u8"), None);
+    }
+}
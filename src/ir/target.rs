@@ -0,0 +1,92 @@
+use inkwell::{
+    attributes::{Attribute, AttributeLoc},
+    module::Module,
+    passes::PassBuilderOptions,
+    targets::{CodeModel, InitializationConfig, RelocMode, Target, TargetMachine, TargetTriple},
+    OptimizationLevel,
+};
+
+/// Create target machine for target triple, like `aarch64-unknown-linux-gnu`.
+/// If triple isn't specified, machine is created for the host
+pub fn target_machine(
+    triple: Option<&str>,
+    optimization: OptimizationLevel,
+) -> Result<TargetMachine, String> {
+    let config = InitializationConfig::default();
+    let (triple, cpu, features) = match triple {
+        Some(triple) => {
+            Target::initialize_all(&config);
+            (
+                TargetTriple::create(triple),
+                "generic".to_string(),
+                String::new(),
+            )
+        }
+        None => {
+            Target::initialize_native(&config)?;
+            (
+                TargetMachine::get_default_triple(),
+                TargetMachine::get_host_cpu_name().to_string(),
+                TargetMachine::get_host_cpu_features().to_string(),
+            )
+        }
+    };
+
+    let target = Target::from_triple(&triple).map_err(|e| e.to_string())?;
+    target
+        .create_target_machine(
+            &triple,
+            &cpu,
+            &features,
+            optimization,
+            RelocMode::PIC,
+            CodeModel::Default,
+        )
+        .ok_or_else(|| {
+            format!(
+                "Can't create target machine for `{}`",
+                triple.as_str().to_string_lossy()
+            )
+        })
+}
+
+/// Set triple and data layout of the module to the ones of target machine
+pub fn set_target(module: &Module, machine: &TargetMachine) {
+    module.set_triple(&machine.get_triple());
+    module.set_data_layout(&machine.get_target_data().get_data_layout());
+}
+
+/// Run optimization pipeline on module.
+///
+/// `pipeline` is either `default` for per-module optimizations
+/// or `lto` for link-time optimizations of merged module
+pub fn optimize(
+    module: &Module,
+    machine: &TargetMachine,
+    pipeline: &str,
+    level: u8,
+) -> Result<(), String> {
+    module
+        .run_passes(
+            &format!("{pipeline}<O{level}>"),
+            machine,
+            PassBuilderOptions::create(),
+        )
+        .map_err(|e| e.to_string())
+}
+
+/// Instrument all functions, defined in module, with AddressSanitizer
+pub fn sanitize_address(module: &Module, machine: &TargetMachine) -> Result<(), String> {
+    let llvm = module.get_context();
+    let kind = Attribute::get_named_enum_kind_id("sanitize_address");
+    for function in module
+        .get_functions()
+        .filter(|f| f.count_basic_blocks() > 0)
+    {
+        function.add_attribute(AttributeLoc::Function, llvm.create_enum_attribute(kind, 0));
+    }
+
+    module
+        .run_passes("asan", machine, PassBuilderOptions::create())
+        .map_err(|e| e.to_string())
+}
@@ -1,8 +1,9 @@
 use inkwell::module::Linkage;
-use inkwell::types::BasicMetadataTypeEnum;
+use inkwell::types::{AnyType, BasicMetadataTypeEnum};
 
 use inkwell::values::BasicMetadataValueEnum;
 use inkwell::values::CallSiteValue;
+use inkwell::values::FunctionValue;
 use log::trace;
 
 use super::inkwell::*;
@@ -17,6 +18,10 @@ use crate::DataHolder;
 use super::Context;
 use super::FunctionContext;
 use super::ModuleContext;
+use super::{
+    define_memory_intrinsic, define_numeric_intrinsic, define_pointer_intrinsic,
+    small_integer_bits, with_small_integer_fast_path, POINTER_INTRINSICS,
+};
 
 /// Trait for lowering to IR within some context
 pub trait ToIR<'llvm, C: Context<'llvm>> {
@@ -241,10 +246,10 @@ impl<'llvm, C: Context<'llvm>> ToIR<'llvm, C> for ClassData {
             return context.types().none().into();
         } else if self.is_bool() {
             return context.types().bool().into();
-        } else if self.is_i32() {
-            return context.types().i32().into();
-        } else if self.is_f64() {
-            return context.types().f64().into();
+        } else if self.is_char() {
+            return context.types().char().into();
+        } else if let Some(number) = self.fixed_width() {
+            return context.types().fixed_width(number).as_any_type_enum();
         }
 
         if self.members.is_empty() {
@@ -394,7 +399,39 @@ impl<'llvm, 'm> ToIR<'llvm, FunctionContext<'llvm, 'm, '_>> for Literal {
                 .bool()
                 .const_int(*value as u64, false)
                 .into(),
+            Literal::Integer { value, ty, .. } if ty.fixed_width().is_some() => {
+                let number = ty.fixed_width().unwrap();
+                context
+                    .types()
+                    .i(number.bits())
+                    .const_int(value.to_u64_wrapping(), false)
+                    .into()
+            }
+            Literal::Rational { value, ty, .. } if ty.fixed_width().is_some() => {
+                let number = ty.fixed_width().unwrap();
+                context
+                    .types()
+                    .fixed_width(number)
+                    .into_float_type()
+                    .const_float(value.to_f64())
+                    .into()
+            }
             Literal::Integer { value, .. } => {
+                if let Some(bits) = value.to_i64().and_then(small_integer_bits) {
+                    let data = context
+                        .types()
+                        .i64()
+                        .const_int(bits, false)
+                        .const_to_pointer(context.types().pointer());
+                    return Some(
+                        context
+                            .types()
+                            .integer()
+                            .const_named_struct(&[data.into()])
+                            .into(),
+                    );
+                }
+
                 if let Some(value) = value.to_i64() {
                     return Some(
                         context
@@ -498,6 +535,40 @@ impl<'llvm, 'm> ToIR<'llvm, FunctionContext<'llvm, 'm, '_>> for VariableReferenc
     }
 }
 
+/// Declare and define pointer intrinsic, called by `call`, for type of pointed values.
+///
+/// Intrinsic is declared for each such type, as its signature depends on it
+fn pointer_intrinsic<'llvm>(
+    call: &Call,
+    context: &mut FunctionContext<'llvm, '_, '_>,
+) -> Option<FunctionValue<'llvm>> {
+    let f = call.function.read().unwrap();
+    let intrinsic = f
+        .mangled_name
+        .as_deref()
+        .filter(|name| POINTER_INTRINSICS.contains(name))?;
+    let element = f.parameters().find_map(|p| match p.ty().without_ref() {
+        Type::Class(c) if c.read().unwrap().basename.as_str() == "Pointer" => {
+            c.read().unwrap().generics().first().cloned()
+        }
+        _ => None,
+    })?;
+
+    let name = format!("{intrinsic}<{}>", element.name());
+    let function = context.functions().get(&name).unwrap_or_else(|| {
+        let mut specialized = f.clone();
+        specialized.mangled_name = Some(name);
+        specialized.declare_global(context.module_context)
+    });
+    // Values of empty types take no space
+    let element = element
+        .to_ir(context)
+        .try_into_basic_type()
+        .unwrap_or_else(|_| context.llvm().struct_type(&[], false).into());
+    define_pointer_intrinsic(context.module(), function, intrinsic, element);
+    Some(function)
+}
+
 impl<'llvm, 'm> ToIR<'llvm, FunctionContext<'llvm, 'm, '_>> for Call {
     type IR = inkwell::values::CallSiteValue<'llvm>;
 
@@ -505,9 +576,12 @@ impl<'llvm, 'm> ToIR<'llvm, FunctionContext<'llvm, 'm, '_>> for Call {
     fn to_ir(&self, context: &mut FunctionContext<'llvm, 'm, '_>) -> Self::IR {
         trace!(target: "to_ir", "{self}");
 
-        let function = context
-            .functions()
-            .get(&self.function.read().unwrap().mangled_name())
+        let function = pointer_intrinsic(self, context)
+            .or_else(|| {
+                context
+                    .functions()
+                    .get(&self.function.read().unwrap().mangled_name())
+            })
             .unwrap_or_else(|| {
                 if self.generic.is_none() {
                     self.function
@@ -533,7 +607,11 @@ impl<'llvm, 'm> ToIR<'llvm, FunctionContext<'llvm, 'm, '_>> for Call {
                 }
             });
 
-        let arguments = self
+        define_memory_intrinsic(context.module(), function);
+        define_numeric_intrinsic(context.module(), function);
+        let function = with_small_integer_fast_path(context.module(), function).unwrap_or(function);
+
+        let mut arguments = self
             .args
             .iter()
             .zip(self.function.read().unwrap().parameters().map(|p| p.ty()))
@@ -547,6 +625,18 @@ impl<'llvm, 'm> ToIR<'llvm, FunctionContext<'llvm, 'm, '_>> for Call {
             })
             .collect::<Vec<BasicMetadataValueEnum>>();
 
+        // Failed assertion reports where it is, instead of where runtime is
+        let function = if function.get_name().to_bytes() == b"assert" {
+            let location = context
+                .builder
+                .build_global_string_ptr(&context.debug().source_location(self.start()), "")
+                .unwrap();
+            arguments.push(location.as_pointer_value().into());
+            context.functions().assert_at()
+        } else {
+            function
+        };
+
         context
             .builder
             .build_call(function, &arguments, "")
@@ -684,8 +774,8 @@ impl<'llvm, 'm> ToIR<'llvm, FunctionContext<'llvm, 'm, '_>> for Expression {
                 if cl.is_opaque()
                     && !(cl.is_none()
                         || cl.is_bool()
-                        || cl.is_i32()
-                        || cl.is_f64()
+                        || cl.is_char()
+                        || cl.fixed_width().is_some()
                         || self.is_reference())
                 {
                     return Some(ptr.into());
@@ -1038,6 +1128,18 @@ impl<'llvm> HIRModuleLowering<'llvm> for ModuleData {
             }
         });
 
+        // Initializes global variables without executing statements, like test harness does
+        let initialize = context.module.add_function(
+            &format!("{name}.initialize"),
+            context.types().none().fn_type(&[], false),
+            None,
+        );
+        FunctionContext::new(&mut context, initialize, at).run(|context| {
+            for init in context.module_context.initializers.clone() {
+                init.to_ir(context);
+            }
+        });
+
         if with_main {
             let main = context.module.add_function(
                 "main",
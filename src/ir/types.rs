@@ -1,9 +1,11 @@
 use inkwell::{
     context::ContextRef,
-    types::{FloatType, IntType, PointerType, StructType, VoidType},
+    types::{BasicTypeEnum, FloatType, IntType, PointerType, StructType, VoidType},
     AddressSpace,
 };
 
+use crate::hir::FixedWidth;
+
 /// LLVM IR for PPL's types
 pub struct Types<'llvm> {
     /// LLVM context
@@ -26,6 +28,11 @@ impl<'llvm> Types<'llvm> {
         self.llvm.bool_type()
     }
 
+    /// LLVM type for unicode scalar value
+    pub fn char(&self) -> IntType<'llvm> {
+        self.u32()
+    }
+
     /// LLVM int type
     pub fn i(&self, bits: u32) -> IntType<'llvm> {
         self.llvm.custom_width_int_type(bits)
@@ -56,11 +63,27 @@ impl<'llvm> Types<'llvm> {
         self.u(64)
     }
 
+    /// LLVM 32-bit float type
+    pub fn f32(&self) -> FloatType<'llvm> {
+        self.llvm.f32_type()
+    }
+
     /// LLVM 64-bit float type
     pub fn f64(&self) -> FloatType<'llvm> {
         self.llvm.f64_type()
     }
 
+    /// LLVM IR for builtin fixed-width number type
+    pub fn fixed_width(&self, number: FixedWidth) -> BasicTypeEnum<'llvm> {
+        match number {
+            FixedWidth::Signed(bits) => self.i(bits).into(),
+            FixedWidth::Unsigned(bits) => self.u(bits).into(),
+            FixedWidth::Float(32) => self.f32().into(),
+            FixedWidth::Float(64) => self.f64().into(),
+            FixedWidth::Float(bits) => unreachable!("There is no {bits}-bit float type"),
+        }
+    }
+
     /// Get LLVM opaque struct type or create it if it doesn't exist
     fn get_or_add_opaque_struct(&self, name: &str) -> StructType<'llvm> {
         if let Some(ty) = self.llvm.get_struct_type(name) {
@@ -21,6 +21,14 @@ pub mod ir;
 
 pub mod compilation;
 
+pub mod doc;
+
+pub mod lsp;
+
+pub mod explain;
+
+pub mod repl;
+
 pub mod from_decimal;
 
 pub mod driver;
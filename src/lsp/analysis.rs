@@ -0,0 +1,673 @@
+use std::{
+    collections::HashMap,
+    ops::Range,
+    panic::{catch_unwind, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use derive_visitor::{DriveMut, VisitorMut};
+use lsp_types::{
+    CompletionItem, CompletionItemKind, Diagnostic, DiagnosticSeverity, DocumentSymbol, Hover,
+    HoverContents, Location, MarkupContent, MarkupKind, NumberOrString, Position, SymbolKind, Url,
+};
+use miette::NamedSource;
+
+use crate::{
+    ast,
+    compilation::{Compiler, Module},
+    hir::{
+        self, Basename, Class, ClassOrTrait, Expression, FunctionData, FunctionNamePart,
+        ParameterOrVariable, Type, Typed,
+    },
+    named::Named,
+    semantics::FindDeclaration,
+    syntax::Ranged,
+    DataHolder, SourceFile,
+};
+
+use super::{offset_of, range_of};
+
+/// Opened text document
+struct Document {
+    /// Current text of the document
+    text: String,
+    /// Root of the package, this document belongs to
+    root: PathBuf,
+    /// Last successfully compiled module and text it was compiled from
+    compiled: Option<(Module, String)>,
+}
+
+/// State of all opened documents and compilers for their packages
+#[derive(Default)]
+pub struct Workspace {
+    /// Compilers by roots of packages
+    compilers: HashMap<PathBuf, Compiler>,
+    /// Opened documents
+    documents: HashMap<Url, Document>,
+}
+
+impl Workspace {
+    /// Create workspace without opened documents
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set text of document and analyze it
+    pub fn update(&mut self, uri: Url, text: String) -> Vec<Diagnostic> {
+        let path = uri
+            .to_file_path()
+            .unwrap_or_else(|_| PathBuf::from(uri.path()));
+        let root = package_root(&path);
+
+        let compiled = self.documents.remove(&uri).and_then(|d| d.compiled);
+        let mut document = Document {
+            text,
+            root,
+            compiled,
+        };
+
+        let source_file = SourceFile::in_memory(NamedSource::new(
+            path.to_string_lossy(),
+            document.text.clone(),
+        ));
+        let compiler = self
+            .compilers
+            .entry(document.root.clone())
+            .or_insert_with_key(|root| compiler_for(root));
+        let result = catch_unwind(AssertUnwindSafe(|| compiler.compile_source(source_file)));
+
+        let diagnostics = match result {
+            Ok(Ok(module)) => {
+                document.compiled = Some((module, document.text.clone()));
+                vec![]
+            }
+            Ok(Err(error)) => {
+                let mut diagnostics = vec![];
+                to_diagnostics(error.as_ref(), &document.text, &mut diagnostics);
+                diagnostics
+            }
+            Err(panic) => {
+                // State of compiler is unknown after panic
+                self.compilers.remove(&document.root);
+                document.compiled = None;
+
+                let reason = panic
+                    .downcast_ref::<String>()
+                    .cloned()
+                    .or_else(|| panic.downcast_ref::<&str>().map(|s| s.to_string()))
+                    .unwrap_or_default();
+                vec![Diagnostic {
+                    severity: Some(DiagnosticSeverity::ERROR),
+                    source: Some("ppl".to_string()),
+                    message: format!("internal compiler error: {reason}"),
+                    ..Default::default()
+                }]
+            }
+        };
+
+        self.documents.insert(uri, document);
+        diagnostics
+    }
+
+    /// Forget closed document
+    pub fn close(&mut self, uri: &Url) {
+        self.documents.remove(uri);
+    }
+
+    /// Drop all cached compilers.
+    /// They will be recreated on next update of documents
+    pub fn reset(&mut self) {
+        self.compilers.clear();
+        self.documents.values_mut().for_each(|d| d.compiled = None);
+    }
+
+    /// Module, compiled from the current text of document
+    fn analyzed(&mut self, uri: &Url) -> Option<(&Document, &mut Compiler, Module)> {
+        let document = self.documents.get(uri)?;
+        let (module, text) = document.compiled.as_ref()?;
+        if *text != document.text {
+            return None;
+        }
+        let compiler = self.compilers.get_mut(&document.root)?;
+        Some((document, compiler, *module))
+    }
+
+    /// Type and documentation of expression under cursor
+    pub fn hover(&mut self, uri: &Url, position: Position) -> Option<Hover> {
+        let (document, compiler, module) = self.analyzed(uri)?;
+        let offset = offset_of(&document.text, position);
+        let expression = expression_at(compiler, module, offset)?;
+
+        let (code, docs) = match &expression {
+            Expression::Call(call) => {
+                let f = call.function.read().unwrap();
+                let docs = f.docs.clone().or_else(|| {
+                    call.generic
+                        .as_ref()
+                        .and_then(|g| g.read().unwrap().docs.clone())
+                });
+                (signature(&f), docs)
+            }
+            Expression::VariableReference(r) => {
+                (format!("{}: {}", r.variable.name(), r.variable.ty()), None)
+            }
+            Expression::MemberReference(m) => (format!("{}: {}", m.member.name(), m.ty()), None),
+            Expression::TypeReference(t) => type_hover(&t.referenced_type),
+            Expression::Constructor(c) => type_hover(&c.ty.referenced_type),
+            other => (other.ty().to_string(), None),
+        };
+
+        let mut value = format!("```ppl\n{code}\n```");
+        if let Some(docs) = docs {
+            value.push_str(&format!("\n\n---\n\n{docs}"));
+        }
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value,
+            }),
+            range: Some(range_of(&document.text, expression.range())),
+        })
+    }
+
+    /// Location of declaration for entity under cursor
+    pub fn definition(&mut self, uri: &Url, position: Position) -> Option<Location> {
+        let (document, compiler, module) = self.analyzed(uri)?;
+        let offset = offset_of(&document.text, position);
+        let expression = expression_at(compiler, module, offset);
+        let locator = Locator {
+            compiler,
+            current: module,
+            uri,
+        };
+
+        if let Some(expression) = expression {
+            let location = match expression {
+                Expression::Call(call) => {
+                    locator.function(&call.generic.unwrap_or(call.function).read().unwrap())
+                }
+                Expression::VariableReference(r) => locator.variable(&r.variable),
+                Expression::MemberReference(m) => {
+                    let name = m.member.read().unwrap().name.range();
+                    match m.base.ty().without_ref() {
+                        Type::Class(c) => locator
+                            .class_module(&c)
+                            .and_then(|module| locator.location(module, name)),
+                        _ => None,
+                    }
+                }
+                Expression::TypeReference(t) => locator.ty(&t.referenced_type),
+                Expression::Constructor(c) => locator.ty(&c.ty.referenced_type),
+                _ => None,
+            };
+            if location.is_some() {
+                return location;
+            }
+        }
+
+        let word = word_at(&document.text, offset);
+        let data = module.data(locator.compiler);
+        if let Some(variable) = data.find_variable(word) {
+            return locator.variable(&variable);
+        }
+        locator.ty(&data.find_type(word)?)
+    }
+
+    /// Completion of function name parts, variables and types
+    pub fn completion(&self, uri: &Url, position: Position) -> Vec<CompletionItem> {
+        let Some(document) = self.documents.get(uri) else {
+            return vec![];
+        };
+        let Some((module, _)) = &document.compiled else {
+            return vec![];
+        };
+        let Some(compiler) = self.compilers.get(&document.root) else {
+            return vec![];
+        };
+        let data = module.data(compiler);
+
+        let offset = offset_of(&document.text, position);
+        let line_start = document.text[..offset].rfind('\n').map_or(0, |n| n + 1);
+        let line = &document.text[line_start..offset];
+        let prefix_start = line
+            .char_indices()
+            .rev()
+            .take_while(|(_, c)| is_identifier_char(*c))
+            .last()
+            .map_or(line.len(), |(i, _)| i);
+        let prefix = &line[prefix_start..];
+        let previous_word = line[..prefix_start].split_whitespace().last();
+
+        let mut items: Vec<CompletionItem> = vec![];
+        let mut push = |item: CompletionItem| {
+            if !items
+                .iter()
+                .any(|i| i.label == item.label && i.detail == item.detail)
+            {
+                items.push(item)
+            }
+        };
+
+        for f in data.iter_functions() {
+            let f = f.read().unwrap();
+            if f.name().starts_with('$') {
+                continue;
+            }
+
+            let mut previous_text = None;
+            for part in f.name_parts() {
+                let FunctionNamePart::Text(text) = part else {
+                    continue;
+                };
+
+                // Next part of already started mixfix call
+                let continues = previous_text.is_some() && previous_text == previous_word;
+                if text.as_str().starts_with(prefix) && (continues || !prefix.is_empty()) {
+                    push(CompletionItem {
+                        label: text.as_str().to_string(),
+                        kind: Some(CompletionItemKind::FUNCTION),
+                        detail: Some(f.name().to_string()),
+                        sort_text: Some(format!("{}{text}", if continues { 0 } else { 1 })),
+                        ..Default::default()
+                    });
+                }
+                previous_text = Some(text.as_str());
+            }
+        }
+
+        if prefix.is_empty() {
+            return items;
+        }
+
+        for (name, variable) in &data.variables {
+            if name.starts_with(prefix) {
+                push(CompletionItem {
+                    label: name.to_string(),
+                    kind: Some(CompletionItemKind::VARIABLE),
+                    detail: Some(variable.ty().to_string()),
+                    sort_text: Some(format!("1{name}")),
+                    ..Default::default()
+                });
+            }
+        }
+
+        for (name, ty) in &data.types {
+            if name.starts_with(prefix) {
+                let kind = match ty {
+                    ClassOrTrait::Class(_) => CompletionItemKind::CLASS,
+                    ClassOrTrait::Trait(_) => CompletionItemKind::INTERFACE,
+                };
+                push(CompletionItem {
+                    label: name.to_string(),
+                    kind: Some(kind),
+                    sort_text: Some(format!("1{name}")),
+                    ..Default::default()
+                });
+            }
+        }
+
+        items
+    }
+
+    /// Declarations of document, as they are written in its text
+    pub fn symbols(&self, uri: &Url) -> Vec<DocumentSymbol> {
+        let Some(document) = self.documents.get(uri) else {
+            return vec![];
+        };
+        let text = &document.text;
+        let Ok(module) = text.parse::<ast::Module>() else {
+            return vec![];
+        };
+
+        module
+            .statements
+            .iter()
+            .filter_map(|statement| match statement {
+                ast::Statement::Declaration(declaration) => {
+                    Some(declaration_symbol(declaration, text))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Root of the package, that contains file at `path`.
+///
+/// It's the parent of the nearest `src` directory
+fn package_root(path: &Path) -> PathBuf {
+    path.ancestors()
+        .find(|dir| dir.file_name().is_some_and(|name| name == "src"))
+        .and_then(|src| src.parent())
+        .or_else(|| path.parent())
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| std::env::current_dir().unwrap_or_default())
+}
+
+/// Create compiler for package at `root`
+fn compiler_for(root: &Path) -> Compiler {
+    if root.file_name().is_some_and(|name| name == "ppl") {
+        Compiler::without_builtin().at(root)
+    } else {
+        Compiler::new().at(root)
+    }
+}
+
+/// Convert compilation error into LSP diagnostics.
+///
+/// Related errors are reported separately
+fn to_diagnostics(error: &dyn miette::Diagnostic, source: &str, out: &mut Vec<Diagnostic>) {
+    let related: Vec<_> = error.related().into_iter().flatten().collect();
+    if !related.is_empty() {
+        related
+            .into_iter()
+            .for_each(|e| to_diagnostics(e, source, out));
+        return;
+    }
+
+    let message = error.to_string();
+    let labels: Vec<_> = error.labels().into_iter().flatten().collect();
+    let range = labels
+        .first()
+        .map_or(0..0, |label| label.offset()..label.offset() + label.len());
+
+    let mut details = vec![message.clone()];
+    details.extend(
+        labels
+            .iter()
+            .filter_map(|label| label.label())
+            .filter(|label| *label != message)
+            .map(str::to_string),
+    );
+    details.extend(error.help().map(|help| format!("help: {help}")));
+
+    out.push(Diagnostic {
+        range: range_of(source, range),
+        severity: Some(DiagnosticSeverity::ERROR),
+        code: error
+            .code()
+            .map(|code| NumberOrString::String(code.to_string())),
+        source: Some("ppl".to_string()),
+        message: details.join("\n"),
+        ..Default::default()
+    });
+}
+
+/// Visitor, that finds the innermost expression at offset
+#[derive(VisitorMut)]
+#[visitor(Expression(enter))]
+struct ExpressionAt {
+    /// Offset to look for
+    offset: usize,
+    /// Innermost expression found so far
+    found: Option<Expression>,
+}
+
+impl ExpressionAt {
+    fn enter_expression(&mut self, expression: &mut Expression) {
+        let range = expression.range();
+        if !(range.start <= self.offset && self.offset <= range.end) {
+            return;
+        }
+
+        let inner = self.found.as_ref().map_or(true, |found| {
+            let outer = found.range();
+            outer.start <= range.start && range.end <= outer.end
+        });
+        if inner {
+            self.found = Some(expression.clone());
+        }
+    }
+}
+
+/// Find the innermost expression of compiled module at offset
+fn expression_at(compiler: &mut Compiler, module: Module, offset: usize) -> Option<Expression> {
+    let mut visitor = ExpressionAt {
+        offset,
+        found: None,
+    };
+    compiler.modules[module.index()].drive_mut(&mut visitor);
+    visitor.found
+}
+
+/// Signature of function in its mixfix form
+fn signature(f: &FunctionData) -> String {
+    let generics = if f.generic_types.is_empty() {
+        String::new()
+    } else {
+        let generics: Vec<_> = f.generic_types.iter().map(|ty| format!("{ty:+}")).collect();
+        format!("<{}>", generics.join(", "))
+    };
+    let name_parts: Vec<_> = f.name_parts().iter().map(|p| p.to_string()).collect();
+    format!("fn{generics} {} -> {}", name_parts.join(" "), f.return_type)
+}
+
+/// Hover text and documentation for a type
+fn type_hover(ty: &Type) -> (String, Option<String>) {
+    let docs = match ty {
+        Type::Class(c) => {
+            let c = c.read().unwrap();
+            c.docs.clone().or_else(|| {
+                c.specialization_of
+                    .as_ref()
+                    .and_then(|g| g.read().unwrap().docs.clone())
+            })
+        }
+        Type::Trait(t) => t.read().unwrap().docs.clone(),
+        _ => None,
+    };
+    (format!("type {ty}"), docs)
+}
+
+/// Helper for locating declarations in compiled modules
+struct Locator<'a> {
+    /// Compiler with compiled modules
+    compiler: &'a Compiler,
+    /// Module of the document, where search started
+    current: Module,
+    /// Uri of the document, where search started
+    uri: &'a Url,
+}
+
+impl Locator<'_> {
+    /// Location of range in module
+    fn location(&self, module: Module, range: Range<usize>) -> Option<Location> {
+        let source_file = module.data(self.compiler).source_file();
+        let uri = if module == self.current {
+            self.uri.clone()
+        } else {
+            let path = std::fs::canonicalize(source_file.path()).ok()?;
+            Url::from_file_path(path).ok()?
+        };
+        Some(Location {
+            uri,
+            range: range_of(source_file.contents(), range),
+        })
+    }
+
+    /// Location of function name
+    fn function(&self, f: &FunctionData) -> Option<Location> {
+        self.location(f.module, f.name_parts.range())
+    }
+
+    /// Location of variable or parameter name
+    fn variable(&self, variable: &ParameterOrVariable) -> Option<Location> {
+        let variable = match variable {
+            ParameterOrVariable::Parameter(p) => {
+                return self.location(self.current, p.read().unwrap().name_range.clone())
+            }
+            ParameterOrVariable::Variable(v) => v,
+        };
+
+        let module = self
+            .compiler
+            .modules
+            .values()
+            .position(|data| {
+                data.statements.iter().any(|s| {
+                    matches!(
+                        s,
+                        hir::Statement::Declaration(hir::Declaration::Variable(v))
+                            if Arc::ptr_eq(v.inner(), variable.inner())
+                    )
+                })
+            })
+            .map_or(self.current, Module::with_index);
+        self.location(module, variable.read().unwrap().name.range())
+    }
+
+    /// Location of type or trait name
+    fn ty(&self, ty: &Type) -> Option<Location> {
+        match ty.without_ref() {
+            Type::Class(c) => {
+                let module = self.class_module(&c)?;
+                let generic = c.read().unwrap().specialization_of.clone();
+                let range = generic.unwrap_or(c).read().unwrap().basename.range();
+                self.location(module, range)
+            }
+            Type::Trait(t) => {
+                let t = t.read().unwrap();
+                self.location(t.module, t.name.range())
+            }
+            _ => None,
+        }
+    }
+
+    /// Module, where class is declared
+    fn class_module(&self, class: &Class) -> Option<Module> {
+        let class = class
+            .read()
+            .unwrap()
+            .specialization_of
+            .clone()
+            .unwrap_or(class.clone());
+        let keyword = class.read().unwrap().keyword.start();
+        self.compiler
+            .modules
+            .values()
+            .position(|data| {
+                data.statements.iter().any(|s| {
+                    matches!(
+                        s,
+                        hir::Statement::Declaration(hir::Declaration::Type(c))
+                            if c.basename() == class.basename()
+                                && c.read().unwrap().keyword.start() == keyword
+                    )
+                })
+            })
+            .map(Module::with_index)
+    }
+}
+
+/// Can character be part of identifier?
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Identifier at offset
+fn word_at(text: &str, offset: usize) -> &str {
+    let start = text[..offset]
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| is_identifier_char(*c))
+        .last()
+        .map_or(offset, |(i, _)| i);
+    let end = text[offset..]
+        .char_indices()
+        .find(|(_, c)| !is_identifier_char(*c))
+        .map_or(text.len(), |(i, _)| offset + i);
+    &text[start..end]
+}
+
+/// Document symbol for range of source.
+///
+/// Range of symbol is extended to contain its selection range
+#[allow(deprecated)]
+fn symbol(
+    source: &str,
+    name: String,
+    kind: SymbolKind,
+    range: Range<usize>,
+    selection: Range<usize>,
+    children: Vec<DocumentSymbol>,
+) -> DocumentSymbol {
+    let range = range.start.min(selection.start)..range.end.max(selection.end);
+    DocumentSymbol {
+        name,
+        detail: None,
+        kind,
+        tags: None,
+        deprecated: None,
+        range: range_of(source, range),
+        selection_range: range_of(source, selection),
+        children: (!children.is_empty()).then_some(children),
+    }
+}
+
+/// Document symbol for declaration
+fn declaration_symbol(declaration: &ast::Declaration, source: &str) -> DocumentSymbol {
+    match declaration {
+        ast::Declaration::Variable(v) => symbol(
+            source,
+            v.name.to_string(),
+            SymbolKind::VARIABLE,
+            v.range(),
+            v.name.range(),
+            vec![],
+        ),
+        ast::Declaration::Function(f) => function_symbol(f, SymbolKind::FUNCTION, source),
+        ast::Declaration::Type(ty) => {
+            let members = ty
+                .members
+                .iter()
+                .map(|m| {
+                    symbol(
+                        source,
+                        m.name.to_string(),
+                        SymbolKind::FIELD,
+                        m.range(),
+                        m.name.range(),
+                        vec![],
+                    )
+                })
+                .collect();
+            symbol(
+                source,
+                ty.name.to_string(),
+                SymbolKind::STRUCT,
+                ty.range(),
+                ty.name.range(),
+                members,
+            )
+        }
+        ast::Declaration::Trait(tr) => {
+            let functions = tr
+                .functions
+                .iter()
+                .map(|f| function_symbol(f, SymbolKind::METHOD, source))
+                .collect();
+            symbol(
+                source,
+                tr.name.to_string(),
+                SymbolKind::INTERFACE,
+                tr.range(),
+                tr.name.range(),
+                functions,
+            )
+        }
+    }
+}
+
+/// Document symbol for function, named as it's written in source
+fn function_symbol(f: &ast::FunctionDeclaration, kind: SymbolKind, source: &str) -> DocumentSymbol {
+    let name = f.name_parts.range();
+    symbol(
+        source,
+        source[name.clone()].to_string(),
+        kind,
+        f.range(),
+        name,
+        vec![],
+    )
+}
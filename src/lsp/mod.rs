@@ -0,0 +1,8 @@
+mod position;
+pub use position::*;
+
+mod analysis;
+pub use analysis::*;
+
+mod server;
+pub use server::*;
@@ -0,0 +1,80 @@
+use std::ops::Range;
+
+use lsp_types::Position;
+
+/// Convert byte offset in source to LSP position.
+///
+/// LSP counts characters in UTF-16 code units
+pub fn position_of(source: &str, offset: usize) -> Position {
+    let mut offset = offset.min(source.len());
+    while !source.is_char_boundary(offset) {
+        offset -= 1;
+    }
+
+    let before = &source[..offset];
+    let line_start = before.rfind('\n').map_or(0, |n| n + 1);
+    Position {
+        line: before.matches('\n').count() as u32,
+        character: before[line_start..].encode_utf16().count() as u32,
+    }
+}
+
+/// Convert LSP position to byte offset in source.
+///
+/// Positions outside of source are clamped to the nearest valid offset
+pub fn offset_of(source: &str, position: Position) -> usize {
+    let mut line_start = 0;
+    for _ in 0..position.line {
+        match source[line_start..].find('\n') {
+            Some(n) => line_start += n + 1,
+            None => return source.len(),
+        }
+    }
+
+    let line_end = source[line_start..]
+        .find('\n')
+        .map_or(source.len(), |n| line_start + n);
+    let mut units = 0;
+    for (i, c) in source[line_start..line_end].char_indices() {
+        if units >= position.character as usize {
+            return line_start + i;
+        }
+        units += c.len_utf16();
+    }
+    line_end
+}
+
+/// Convert byte range in source to LSP range
+pub fn range_of(source: &str, range: Range<usize>) -> lsp_types::Range {
+    lsp_types::Range {
+        start: position_of(source, range.start),
+        end: position_of(source, range.end),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn positions() {
+        let source = "let x = 1\nprintln \"привет\" + x\n";
+
+        assert_eq!(position_of(source, 0), Position::new(0, 0));
+        assert_eq!(position_of(source, 4), Position::new(0, 4));
+        assert_eq!(position_of(source, 10), Position::new(1, 0));
+
+        let x = source.rfind('x').unwrap();
+        assert_eq!(position_of(source, x), Position::new(1, 19));
+        assert_eq!(offset_of(source, Position::new(1, 19)), x);
+
+        assert_eq!(offset_of(source, Position::new(0, 100)), 9);
+        assert_eq!(offset_of(source, Position::new(5, 0)), source.len());
+        assert_eq!(
+            range_of(source, 4..5),
+            lsp_types::Range::new(Position::new(0, 4), Position::new(0, 5))
+        );
+    }
+}
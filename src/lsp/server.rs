@@ -0,0 +1,216 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::{
+    notification::{
+        DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument,
+        Notification as LspNotification, PublishDiagnostics,
+    },
+    request::{
+        Completion, DocumentSymbolRequest, GotoDefinition, HoverRequest, Request as LspRequest,
+    },
+    CompletionOptions, CompletionParams, CompletionResponse, Diagnostic,
+    DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
+    DocumentSymbolParams, DocumentSymbolResponse, GotoDefinitionParams, GotoDefinitionResponse,
+    HoverParams, HoverProviderCapability, OneOf, PublishDiagnosticsParams, ServerCapabilities,
+    TextDocumentSyncCapability, TextDocumentSyncKind, Url,
+};
+use miette::miette;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use super::Workspace;
+
+/// Capabilities, supported by PPL language server
+pub fn capabilities() -> ServerCapabilities {
+    ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        completion_provider: Some(CompletionOptions::default()),
+        document_symbol_provider: Some(OneOf::Left(true)),
+        ..Default::default()
+    }
+}
+
+/// Run language server over stdin and stdout, until client asks it to exit
+pub fn run_stdio() -> miette::Result<()> {
+    let (connection, io_threads) = Connection::stdio();
+
+    let capabilities = serde_json::to_value(capabilities()).unwrap();
+    connection
+        .initialize(capabilities)
+        .map_err(|e| miette!("Can't initialize language server: {e}"))?;
+
+    Server::new(connection).run()?;
+    io_threads
+        .join()
+        .map_err(|e| miette!("Language server IO error: {e}"))
+}
+
+/// Language server, that handles messages from connection
+pub struct Server {
+    /// Connection to the client
+    connection: Connection,
+    /// Opened documents and their analysis
+    workspace: Workspace,
+}
+
+impl Server {
+    /// Create server for already initialized connection
+    pub fn new(connection: Connection) -> Self {
+        Self {
+            connection,
+            workspace: Workspace::new(),
+        }
+    }
+
+    /// Handle messages until shutdown
+    pub fn run(mut self) -> miette::Result<()> {
+        let receiver = self.connection.receiver.clone();
+        for message in receiver {
+            match message {
+                Message::Request(request) => {
+                    let shutdown = self
+                        .connection
+                        .handle_shutdown(&request)
+                        .map_err(|e| miette!("{e}"))?;
+                    if shutdown {
+                        return Ok(());
+                    }
+
+                    let response = self.handle_request(request);
+                    self.send(response.into())?;
+                }
+                Message::Notification(notification) => self.handle_notification(notification)?,
+                Message::Response(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Send message to the client
+    fn send(&self, message: Message) -> miette::Result<()> {
+        self.connection
+            .sender
+            .send(message)
+            .map_err(|e| miette!("Can't send message to client: {e}"))
+    }
+
+    /// Compute response for request
+    fn handle_request(&mut self, request: Request) -> Response {
+        let id = request.id.clone();
+        let method = request.method.clone();
+        let workspace = &mut self.workspace;
+        let result = catch_unwind(AssertUnwindSafe(|| match method.as_str() {
+            HoverRequest::METHOD => params::<HoverParams>(request).map(|p| {
+                let p = p.text_document_position_params;
+                to_value(workspace.hover(&p.text_document.uri, p.position))
+            }),
+            GotoDefinition::METHOD => params::<GotoDefinitionParams>(request).map(|p| {
+                let p = p.text_document_position_params;
+                let location = workspace.definition(&p.text_document.uri, p.position);
+                to_value(location.map(GotoDefinitionResponse::Scalar))
+            }),
+            Completion::METHOD => params::<CompletionParams>(request).map(|p| {
+                let p = p.text_document_position;
+                let items = workspace.completion(&p.text_document.uri, p.position);
+                to_value(CompletionResponse::Array(items))
+            }),
+            DocumentSymbolRequest::METHOD => params::<DocumentSymbolParams>(request).map(|p| {
+                let symbols = workspace.symbols(&p.text_document.uri);
+                to_value(DocumentSymbolResponse::Nested(symbols))
+            }),
+            method => Err((
+                ErrorCode::MethodNotFound,
+                format!("Unsupported request `{method}`"),
+            )),
+        }));
+
+        match result {
+            Ok(Ok(value)) => Response::new_ok(id, value),
+            Ok(Err((code, message))) => Response::new_err(id, code as i32, message),
+            Err(_) => {
+                self.workspace.reset();
+                Response::new_err(
+                    id,
+                    ErrorCode::InternalError as i32,
+                    "internal compiler error".to_string(),
+                )
+            }
+        }
+    }
+
+    /// Update documents and publish diagnostics for them
+    fn handle_notification(&mut self, notification: Notification) -> miette::Result<()> {
+        match notification.method.clone().as_str() {
+            DidOpenTextDocument::METHOD => {
+                let Ok(p) = params::<DidOpenTextDocumentParams>(notification) else {
+                    return Ok(());
+                };
+                let uri = p.text_document.uri;
+                let diagnostics = self.workspace.update(uri.clone(), p.text_document.text);
+                self.publish(uri, diagnostics)
+            }
+            DidChangeTextDocument::METHOD => {
+                let Ok(p) = params::<DidChangeTextDocumentParams>(notification) else {
+                    return Ok(());
+                };
+                // Only full text synchronization is supported
+                let Some(change) = p.content_changes.into_iter().last() else {
+                    return Ok(());
+                };
+                let uri = p.text_document.uri;
+                let diagnostics = self.workspace.update(uri.clone(), change.text);
+                self.publish(uri, diagnostics)
+            }
+            DidCloseTextDocument::METHOD => {
+                let Ok(p) = params::<DidCloseTextDocumentParams>(notification) else {
+                    return Ok(());
+                };
+                self.workspace.close(&p.text_document.uri);
+                self.publish(p.text_document.uri, vec![])
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Publish diagnostics for document
+    fn publish(&self, uri: Url, diagnostics: Vec<Diagnostic>) -> miette::Result<()> {
+        let params = PublishDiagnosticsParams {
+            uri,
+            diagnostics,
+            version: None,
+        };
+        self.send(Notification::new(PublishDiagnostics::METHOD.to_string(), params).into())
+    }
+}
+
+/// Message, that has parameters
+trait HasParams {
+    /// Take parameters of message
+    fn into_params(self) -> Value;
+}
+
+impl HasParams for Request {
+    fn into_params(self) -> Value {
+        self.params
+    }
+}
+
+impl HasParams for Notification {
+    fn into_params(self) -> Value {
+        self.params
+    }
+}
+
+/// Deserialize parameters of message
+fn params<P: DeserializeOwned>(message: impl HasParams) -> Result<P, (ErrorCode, String)> {
+    serde_json::from_value(message.into_params())
+        .map_err(|e| (ErrorCode::InvalidParams, e.to_string()))
+}
+
+/// Serialize response
+fn to_value(value: impl serde::Serialize) -> Value {
+    serde_json::to_value(value).unwrap()
+}
@@ -0,0 +1,356 @@
+use std::{
+    cell::{Cell, RefCell},
+    panic::{catch_unwind, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use inkwell::{execution_engine::ExecutionEngine, OptimizationLevel};
+use log::debug;
+use miette::{bail, miette, NamedSource};
+use rustyline::{error::ReadlineError, DefaultEditor};
+
+use crate::{
+    ast::{self, FnKind},
+    compilation::Compiler,
+    driver::{
+        commands::{compile::OutputType, Build},
+        Execute,
+    },
+    hir::{self, ModuleData, Type, Typed},
+    ir::HIRModuleLowering,
+    semantics::{ModuleContext, Monomorphize, ToHIR},
+    syntax::{Context, Identifier, InteractiveLexer, Lexer, Parse, Ranged},
+    SourceFile,
+};
+
+/// Description of REPL commands
+const HELP: &str = "\
+:type <expr>   show type of expression
+:hir           show HIR of statements, executed in this session
+:ir            show LLVM IR of the last executed statement
+:load <file>   execute statements from file
+:reset         forget everything, declared in this session
+:help          show this message
+:quit          exit REPL
+";
+
+/// State of Read-Evaluate-Print Loop
+pub struct Repl<'llvm> {
+    /// Compiler with `ppl` package and modules, used by the session
+    compiler: Compiler,
+    /// Module with declarations of the session
+    module: ModuleData,
+    /// Statements, successfully executed in the session
+    statements: Vec<hir::Statement>,
+    /// IR of the last executed statement
+    last_ir: Option<String>,
+    /// LLVM context for generated code
+    llvm: &'llvm inkwell::context::Context,
+    /// Engine, that executes generated code
+    engine: ExecutionEngine<'llvm>,
+    /// Optimization level for generated code
+    optimization: OptimizationLevel,
+}
+
+impl<'llvm> Repl<'llvm> {
+    /// Create REPL with empty session
+    pub fn new(
+        llvm: &'llvm inkwell::context::Context,
+        optimization: OptimizationLevel,
+    ) -> miette::Result<Self> {
+        let mut compiler = Compiler::new();
+        let module = Self::empty_module(&mut compiler);
+        Ok(Self {
+            compiler,
+            module,
+            statements: vec![],
+            last_ir: None,
+            llvm,
+            engine: Self::engine(llvm, optimization)?,
+            optimization,
+        })
+    }
+
+    /// Source file of session's module
+    fn source_file() -> SourceFile {
+        SourceFile::in_memory(NamedSource::new("repl", String::new()))
+    }
+
+    /// Module of a new session with builtin module imported
+    fn empty_module(compiler: &mut Compiler) -> ModuleData {
+        ModuleContext::new(ModuleData::new(Self::source_file()), compiler).module
+    }
+
+    /// Create engine for executing generated code
+    fn engine(
+        llvm: &'llvm inkwell::context::Context,
+        optimization: OptimizationLevel,
+    ) -> miette::Result<ExecutionEngine<'llvm>> {
+        llvm.create_module("")
+            .create_jit_execution_engine(optimization)
+            .map_err(|e| miette!("Can't create execution engine: {e}"))
+    }
+
+    /// Run `f` within context of the session.
+    ///
+    /// Compiler panics are reported as errors and keep the session usable
+    fn within<R>(
+        &mut self,
+        f: impl FnOnce(&mut ModuleContext) -> miette::Result<R>,
+    ) -> miette::Result<R> {
+        let module = std::mem::replace(&mut self.module, ModuleData::new(Self::source_file()));
+        let mut context = ModuleContext {
+            module,
+            compiler: &mut self.compiler,
+        };
+        let result = catch_unwind(AssertUnwindSafe(|| f(&mut context)));
+        self.module = context.module;
+        result.unwrap_or_else(|_| Err(miette!("internal compiler error, input is ignored")))
+    }
+
+    /// Lower, compile and run single statement
+    pub fn execute(&mut self, statement: ast::Statement) -> miette::Result<()> {
+        debug!(target: "ast", "{:#?}", statement);
+
+        let statement = self.within(|context| {
+            let mut hir = statement.to_hir(context)?;
+            // Values without `println` for their type are not printed
+            if let ast::Statement::Expression(value) = &statement
+                && let hir::Statement::Expression(lowered) = &hir
+                && !lowered.ty().is_none()
+                && let Ok(echo) = echo(value.clone()).to_hir(context)
+            {
+                hir = echo;
+            }
+            hir.monomorphize(context);
+            Ok(hir)
+        })?;
+        debug!(target: "hir", "{:#}", statement);
+
+        self.module.statements = vec![statement.clone()];
+        let current_module = self.compiler.current_module();
+        let ir = catch_unwind(AssertUnwindSafe(|| {
+            self.module.to_ir(self.llvm, true, current_module)
+        }));
+        self.module.statements.clear();
+        let ir = ir.map_err(|_| miette!("internal compiler error, statement is not executed"))?;
+        debug!(target: "ir", "{}", ir.to_string());
+        self.last_ir = Some(ir.to_string());
+
+        self.engine
+            .add_module(&ir)
+            .map_err(|_| miette!("Can't add generated code to execution engine"))?;
+        if let Some(main) = ir.get_function("main") {
+            unsafe { self.engine.run_function_as_main(main, &[]) };
+        }
+
+        self.statements.push(statement);
+        Ok(())
+    }
+
+    /// Run command, like `type 1 + 2`, without leading `:`
+    pub fn command(&mut self, command: &str) -> miette::Result<()> {
+        let (name, argument) = command
+            .split_once(char::is_whitespace)
+            .map(|(name, argument)| (name, argument.trim()))
+            .unwrap_or((command, ""));
+        match name {
+            "type" => println!("{}", self.type_of(argument)?),
+            "hir" => print!("{}", self.session()),
+            "ir" => match &self.last_ir {
+                Some(ir) => print!("{ir}"),
+                None => println!("Nothing was executed yet"),
+            },
+            "load" => self.load(Path::new(argument))?,
+            "reset" => self.reset()?,
+            "help" => print!("{HELP}"),
+            _ => bail!("Unknown command `:{name}`. Use `:help` to list commands"),
+        }
+        Ok(())
+    }
+
+    /// Get type of expression without executing it
+    fn type_of(&mut self, expression: &str) -> miette::Result<Type> {
+        let source = || NamedSource::new("type", expression.to_string());
+        let expression = ast::Expression::from_str(expression)
+            .map_err(|e| miette::Report::from(e).with_source_code(source()))?;
+        self.within(|context| Ok(expression.to_hir(context)?.ty()))
+            .map_err(|e| e.with_source_code(source()))
+    }
+
+    /// Module with all statements, executed in the session
+    fn session(&self) -> ModuleData {
+        let mut session = self.module.clone();
+        session.statements = self.statements.clone();
+        session
+    }
+
+    /// Execute statements from file one by one
+    fn load(&mut self, path: &Path) -> miette::Result<()> {
+        if path.as_os_str().is_empty() {
+            bail!("Expected path to file: `:load file.ppl`");
+        }
+
+        let source_file = SourceFile::with_path(path)
+            .map_err(|e| miette!("Can't read {}: {e}", path.display()))?;
+        let module = ast::Module::from_str(source_file.contents())
+            .map_err(|e| miette::Report::from(e).with_source_code(source_file.clone()))?;
+        for statement in module.statements {
+            self.execute(statement)
+                .map_err(|e| e.with_source_code(source_file.clone()))?;
+        }
+        Ok(())
+    }
+
+    /// Forget everything, declared in the session
+    fn reset(&mut self) -> miette::Result<()> {
+        self.module = Self::empty_module(&mut self.compiler);
+        self.statements.clear();
+        self.last_ir = None;
+        // Old engine still has definitions of the session
+        self.engine = Self::engine(self.llvm, self.optimization)?;
+        Ok(())
+    }
+}
+
+/// Statement, that prints value of expression
+fn echo(value: ast::Expression) -> ast::Statement {
+    let println = Identifier::from("println").at(value.start());
+    ast::Statement::Expression(
+        ast::Call {
+            kind: FnKind::Function,
+            name_parts: vec![println.into(), value.into()],
+        }
+        .into(),
+    )
+}
+
+/// Load `ppl` library, so generated code can call it.
+///
+/// # Search order
+/// 1. `{exe_dir}/../lib`, `{exe_dir}/deps` and `{exe_dir}`, where `exe_dir` is directory of `ppl` executable
+/// 2. `ppl` package, built into `ppl-stdlib` in temporary directory. Code from previous builds is reused
+fn load_stdlib() -> miette::Result<()> {
+    let library = OutputType::DynamicLibrary.named("ppl");
+    let prebuilt = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf))
+        .into_iter()
+        .flat_map(|exe_dir| [exe_dir.join("../lib"), exe_dir.join("deps"), exe_dir])
+        .map(|dir| dir.join(&library))
+        .find(|path| path.exists());
+    let path = match prebuilt {
+        Some(path) => path,
+        None => build_stdlib()?,
+    };
+
+    inkwell::support::load_library_permanently(&path)
+        .map_err(|_| miette!("Failed to load core library at: {}", path.display()))
+}
+
+/// Build `ppl` package into `ppl-stdlib` in temporary directory,
+/// so source tree of compiler stays untouched
+fn build_stdlib() -> miette::Result<PathBuf> {
+    let package = Path::new(Compiler::PPL_PACKAGE);
+    let cwd = std::env::current_dir().map_err(|e| miette!("{e}"))?;
+    std::env::set_current_dir(package).map_err(|e| miette!("{e}"))?;
+
+    let library = Build {
+        output_dir: std::env::temp_dir().join("ppl-stdlib"),
+        ..Default::default()
+    }
+    .execute();
+
+    // Relative paths in `:load` must still work
+    std::env::set_current_dir(cwd).map_err(|e| miette!("{e}"))?;
+    library
+}
+
+/// File to keep history of entered lines in.
+/// `PPL_HISTORY` environment variable overrides the default `~/.ppl_history`
+fn history_file() -> Option<PathBuf> {
+    std::env::var_os("PPL_HISTORY")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".ppl_history")))
+}
+
+/// Run Read-Evaluate-Print Loop until the end of input or `:quit`
+pub fn run(optimization: OptimizationLevel) -> miette::Result<()> {
+    load_stdlib()?;
+
+    let llvm = inkwell::context::Context::create();
+    let mut repl = Repl::new(&llvm, optimization)?;
+
+    let editor = RefCell::new(DefaultEditor::new().map_err(|e| miette!("{e}"))?);
+    let history = history_file();
+    if let Some(history) = &history {
+        // There is no history before the first session
+        let _ = editor.borrow_mut().load_history(history);
+    }
+
+    let finished = Cell::new(false);
+    let read_line = |prompt: &str| -> Option<String> {
+        let mut editor = editor.borrow_mut();
+        match editor.readline(prompt) {
+            Ok(line) => {
+                let _ = editor.add_history_entry(line.as_str());
+                Some(line + "\n")
+            }
+            // Interrupted input is treated as empty line
+            Err(ReadlineError::Interrupted) => Some("\n".to_string()),
+            Err(_) => None,
+        }
+    };
+
+    // First line of statement is read before lexing to check for commands
+    let first_line = Cell::new(None);
+    let get_line = || {
+        first_line.take().unwrap_or_else(|| {
+            read_line("... ").unwrap_or_else(|| {
+                finished.set(true);
+                "\n".to_string()
+            })
+        })
+    };
+
+    let mut parse_context = Context::new(InteractiveLexer::new(get_line));
+    while !finished.get() {
+        let Some(line) = read_line(">>> ") else {
+            break;
+        };
+
+        let input = line.trim();
+        let result = if input.is_empty() {
+            continue;
+        } else if matches!(input, ":quit" | ":q") {
+            break;
+        } else if let Some(command) = input.strip_prefix(':') {
+            repl.command(command)
+        } else {
+            first_line.set(Some(line));
+            ast::Statement::parse(&mut parse_context)
+                .map_err(miette::Report::from)
+                .and_then(|statement| repl.execute(statement))
+                .map_err(|e| {
+                    e.with_source_code(NamedSource::new(
+                        "stdin",
+                        parse_context.lexer.source().to_string(),
+                    ))
+                })
+        };
+
+        if let Err(err) = result {
+            println!("{err:?}");
+            parse_context.lexer.go_to_end();
+        }
+    }
+
+    if let Some(history) = &history {
+        editor
+            .borrow_mut()
+            .save_history(history)
+            .map_err(|e| miette!("Can't save history to {}: {e}", history.display()))?;
+    }
+    Ok(())
+}
@@ -1,6 +1,10 @@
 use std::fmt::{self, Display};
 
-use miette::{Diagnostic, LabeledSpan, MietteHandler, ReportHandler, SourceCode};
+use miette::{
+    Diagnostic, LabeledSpan, MietteHandler, NamedSource, ReportHandler, Severity, SourceCode,
+};
+
+use crate::{explain::explanation, SourceFile};
 
 /// Struct to report errors
 pub struct Reporter;
@@ -21,52 +25,58 @@ impl ReportHandler for Reporter {
         // Check that this is an error vector.
         // We want to threat it as just a collection of unrelated errors
         if error.to_string().is_empty() {
-            if let Some(source_code) = error.source_code() {
-                for e in error.related().unwrap() {
-                    handler.debug(
-                        &WithSourceCode {
-                            diagnostic: e,
-                            source_code,
-                        },
-                        f,
-                    )?;
-                }
-            } else {
-                for e in error.related().unwrap() {
-                    handler.debug(e, f)?;
-                }
+            let source_code = error.source_code();
+            for e in error.related().unwrap() {
+                handler.debug(
+                    &TopLevel {
+                        diagnostic: e,
+                        source_code,
+                    },
+                    f,
+                )?;
             }
             Ok(())
         } else {
-            handler.debug(error, f)
+            handler.debug(
+                &TopLevel {
+                    diagnostic: error,
+                    source_code: None,
+                },
+                f,
+            )
         }
     }
 }
 
-struct WithSourceCode<'d, 's> {
+/// Diagnostic, reported by [`Reporter`] on its own.
+///
+/// Refers to explanation of its code in help message
+struct TopLevel<'d, 's> {
+    /// Reported diagnostic
     diagnostic: &'d dyn Diagnostic,
-    source_code: &'s dyn SourceCode,
+    /// Source code of error vector, this diagnostic is part of
+    source_code: Option<&'s dyn SourceCode>,
 }
 
-impl Display for WithSourceCode<'_, '_> {
+impl Display for TopLevel<'_, '_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.diagnostic)
     }
 }
 
-impl fmt::Debug for WithSourceCode<'_, '_> {
+impl fmt::Debug for TopLevel<'_, '_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         std::fmt::Debug::fmt(&self.diagnostic, f)
     }
 }
 
-impl std::error::Error for WithSourceCode<'_, '_> {
+impl std::error::Error for TopLevel<'_, '_> {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         self.diagnostic.source()
     }
 }
 
-impl Diagnostic for WithSourceCode<'_, '_> {
+impl Diagnostic for TopLevel<'_, '_> {
     fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
         self.diagnostic.code()
     }
@@ -76,7 +86,17 @@ impl Diagnostic for WithSourceCode<'_, '_> {
     }
 
     fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
-        self.diagnostic.help()
+        let explain = self
+            .diagnostic
+            .code()
+            .map(|code| code.to_string())
+            .filter(|code| explanation(code).is_some())
+            .map(|code| format!("try `ppl explain {code}`"));
+        match (self.diagnostic.help(), explain) {
+            (Some(help), Some(explain)) => Some(Box::new(format!("{help}\n{explain}"))),
+            (None, Some(explain)) => Some(Box::new(explain)),
+            (help, None) => help,
+        }
     }
 
     fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
@@ -96,6 +116,102 @@ impl Diagnostic for WithSourceCode<'_, '_> {
     }
 
     fn source_code(&self) -> Option<&dyn SourceCode> {
-        self.diagnostic.source_code().or(Some(self.source_code))
+        self.diagnostic.source_code().or(self.source_code)
+    }
+}
+
+/// Struct to report errors as JSON objects, one per error
+pub struct JsonReporter;
+
+impl JsonReporter {
+    /// Render error as JSON objects.
+    /// Error vectors are split into separate errors
+    pub fn render(&self, error: &dyn Diagnostic) -> Vec<serde_json::Value> {
+        self.flatten(error, None)
+    }
+
+    /// Render error and errors, that it consists of
+    fn flatten(
+        &self,
+        error: &dyn Diagnostic,
+        source_code: Option<&dyn SourceCode>,
+    ) -> Vec<serde_json::Value> {
+        let source_code = error.source_code().or(source_code);
+        if !error.to_string().is_empty() {
+            return vec![self.diagnostic(error, source_code)];
+        }
+
+        error
+            .related()
+            .into_iter()
+            .flatten()
+            .flat_map(|e| self.flatten(e, source_code))
+            .collect()
+    }
+
+    /// Render single diagnostic with its related diagnostics
+    fn diagnostic(
+        &self,
+        error: &dyn Diagnostic,
+        source_code: Option<&dyn SourceCode>,
+    ) -> serde_json::Value {
+        let source_code = error.source_code().or(source_code);
+        let severity = match error.severity().unwrap_or(Severity::Error) {
+            Severity::Advice => "advice",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        let labels: Vec<_> = error
+            .labels()
+            .into_iter()
+            .flatten()
+            .map(|label| self.label(&label, source_code))
+            .collect();
+        let related: Vec<_> = error
+            .related()
+            .into_iter()
+            .flatten()
+            .flat_map(|e| self.flatten(e, source_code))
+            .collect();
+
+        serde_json::json!({
+            "code": error.code().map(|code| code.to_string()),
+            "severity": severity,
+            "message": error.to_string(),
+            "help": error.help().map(|help| help.to_string()),
+            "labels": labels,
+            "source": error
+                .diagnostic_source()
+                .map(|source| self.diagnostic(source, source_code)),
+            "related": related,
+        })
+    }
+
+    /// Render label with its location in source code
+    fn label(
+        &self,
+        label: &LabeledSpan,
+        source_code: Option<&dyn SourceCode>,
+    ) -> serde_json::Value {
+        let offset = label.offset();
+        // Source code is only accessible by spans,
+        // so its beginning is read to find line and column of label
+        let source_file = source_code
+            .and_then(|code| code.read_span(&(0, offset).into(), 0, 0).ok())
+            .map(|contents| {
+                SourceFile::in_memory(NamedSource::new(
+                    contents.name().unwrap_or_default(),
+                    String::from_utf8_lossy(contents.data()).into_owned(),
+                ))
+            });
+
+        serde_json::json!({
+            "file": source_file.as_ref().map(SourceFile::name),
+            "line": source_file.as_ref().map(|f| f.line_number(offset).one_based()),
+            "column": source_file.as_ref().map(|f| f.column_number(offset).one_based()),
+            "offset": offset,
+            "length": label.len(),
+            "label": label.label(),
+        })
     }
 }
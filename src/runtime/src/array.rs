@@ -0,0 +1,52 @@
+use crate::Integer;
+
+/// Array of values, created by PPL code.
+///
+/// Runtime functions append to arrays, passed to them by reference,
+/// growing memory the same way `push` does.
+///
+/// # PPL
+/// ```no_run
+/// type Array<T>:
+///     size: Integer
+///     capacity: Integer
+///     data: Pointer<T>
+/// ```
+#[repr(C)]
+pub struct Array<T> {
+    pub size: Integer,
+    pub capacity: Integer,
+    pub data: *mut T,
+}
+
+impl<T> Array<T> {
+    /// Get elements of array
+    pub fn as_slice(&self) -> &[T] {
+        let size = self.size.as_ref().to_usize().unwrap();
+        if size == 0 {
+            return &[];
+        }
+        unsafe { std::slice::from_raw_parts(self.data, size) }
+    }
+
+    /// Append value to the end of array
+    pub fn push(&mut self, value: T) {
+        let size = self.size.as_ref().to_usize().unwrap();
+        let capacity = self.capacity.as_ref().to_usize().unwrap();
+        if size == capacity {
+            let capacity = (capacity * 2).max(8);
+            let bytes = capacity * std::mem::size_of::<T>();
+            self.data = unsafe { libc::realloc(self.data.cast(), bytes) }.cast();
+            self.capacity = capacity.into();
+        }
+
+        unsafe { self.data.add(size).write(value) };
+        self.size = (size + 1).into();
+    }
+}
+
+impl<T> Extend<T> for Array<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, values: I) {
+        values.into_iter().for_each(|value| self.push(value));
+    }
+}
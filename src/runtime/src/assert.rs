@@ -1,3 +1,5 @@
+use std::ffi::{c_char, CStr};
+
 use crate::String;
 
 /// # PPL
@@ -13,3 +15,16 @@ pub extern "C" fn assert(condition: bool, message: &String) {
     }
     assert!(condition, "{message}");
 }
+
+/// Same as [`assert`], but also reports location of assertion, like `src/main.ppl:3:2`.
+/// Calls to `assert` are lowered to it by compiler
+#[no_mangle]
+pub extern "C" fn assert_at(condition: bool, message: &String, location: *const c_char) {
+    let message = unsafe { message.data.as_ref().unwrap() };
+    if !condition {
+        let location = unsafe { CStr::from_ptr(location) }.to_string_lossy();
+        println!("Assertion failed: {message}");
+        println!("    at {location}");
+    }
+    assert!(condition, "{message}");
+}
@@ -1,4 +1,4 @@
-use crate::String;
+use crate::{Integer, String};
 
 /// # PPL
 /// ```no_run
@@ -9,3 +9,46 @@ use crate::String;
 pub extern "C" fn env(name: &String) -> String {
     std::env::var(name.as_ref()).unwrap_or_default().into()
 }
+
+/// Number of command line arguments, excluding name of the program
+///
+/// # PPL
+/// ```no_run
+/// @mangle_as("argument_count")
+/// fn argument count -> Integer
+/// ```
+#[no_mangle]
+pub extern "C" fn argument_count() -> Integer {
+    std::env::args_os().count().saturating_sub(1).into()
+}
+
+/// Get command line argument by index, excluding name of the program.
+/// Returns empty string, if there is no such argument
+///
+/// # PPL
+/// ```no_run
+/// @mangle_as("argument_at")
+/// fn argument <i: Integer> -> String
+/// ```
+#[no_mangle]
+pub extern "C" fn argument_at(i: Integer) -> String {
+    i.as_ref()
+        .to_usize()
+        .and_then(|i| std::env::args_os().nth(i + 1))
+        .map(|arg| arg.to_string_lossy().into_owned())
+        .unwrap_or_default()
+        .into()
+}
+
+/// Terminate the program with exit code.
+/// Codes, that don't fit into `i32`, are replaced with `1`
+///
+/// # PPL
+/// ```no_run
+/// @mangle_as("exit_with")
+/// fn exit with <code: Integer>
+/// ```
+#[no_mangle]
+pub extern "C" fn exit_with(code: Integer) -> ! {
+    std::process::exit(code.as_ref().to_i32().unwrap_or(1))
+}
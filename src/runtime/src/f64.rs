@@ -1,5 +1,6 @@
 use crate::{Rational, String};
 
+type F32 = f32;
 type F64 = f64;
 
 /// Converts `F64` to `String`
@@ -16,64 +17,144 @@ pub extern "C" fn f64_as_string(d: F64) -> String {
     d.to_string().into()
 }
 
-/// Negates f64
+/// Converts `F32` to `String`
 ///
 /// # PPL
 /// ```no_run
-/// @mangle_as("minus_f64")
-/// fn - <:F64> -> F64
+/// /// Convert `F32` to `String`
+/// @mangle_as("f32_as_string")
+/// fn String from <:F32> -> String
 /// ```
 #[no_mangle]
-pub extern "C" fn minus_f64(d: F64) -> F64 {
-    -d
+pub extern "C" fn f32_as_string(d: F32) -> String {
+    d.to_string().into()
 }
 
-/// Add 2 f64s
+/// Create f64 from rational
 ///
 /// # PPL
 /// ```no_run
-/// @mangle_as("f64_plus_f64")
-/// fn <:F64> + <:F64> -> F64
+/// /// Convert `Rational` to `F64`
+/// @mangle_as("f64_from_rational")
+/// fn F64 from <:Rational> -> F64
 /// ```
 #[no_mangle]
-pub extern "C" fn f64_plus_f64(x: F64, y: F64) -> F64 {
-    x + y
+pub extern "C" fn f64_from_rational(r: Rational) -> F64 {
+    r.as_ref().to_f64()
 }
 
-/// Multiply 2 F64s
+/// Create rational from f64
 ///
 /// # PPL
 /// ```no_run
-/// @mangle_as("f64_star_f64")
-/// fn <:F64> * <:F64> -> F64
+/// /// Convert `F64` to `Rational`
+/// @mangle_as("rational_from_f64")
+/// fn Rational from <:F64> -> Rational
 /// ```
 #[no_mangle]
-pub extern "C" fn f64_star_f64(x: F64, y: F64) -> F64 {
-    x * y
+pub extern "C" fn rational_from_f64(d: F64) -> Rational {
+    rug::Rational::from_f64(d).unwrap().into()
 }
 
-/// Create f64 from rational
+/// Create f32 from rational
 ///
 /// # PPL
 /// ```no_run
-/// /// Convert `Rational` to `F64`
-/// @mangle_as("f64_from_rational")
-/// fn F64 from <:Rational> -> F64
+/// /// Convert `Rational` to `F32`
+/// @mangle_as("f32_from_rational")
+/// fn F32 from <:Rational> -> F32
 /// ```
 #[no_mangle]
-pub extern "C" fn f64_from_rational(r: Rational) -> F64 {
-    r.as_ref().to_f64()
+pub extern "C" fn f32_from_rational(r: Rational) -> F32 {
+    r.as_ref().to_f32()
 }
 
-/// Create rational from f64
+/// Create rational from f32
 ///
 /// # PPL
 /// ```no_run
-/// /// Convert `Rational` to `F64`
-/// @mangle_as("rational_from_f64")
-/// fn Rational from <:F64> -> Rational
+/// /// Convert `F32` to `Rational`
+/// @mangle_as("rational_from_f32")
+/// fn Rational from <:F32> -> Rational
 /// ```
 #[no_mangle]
-pub extern "C" fn rational_from_f64(d: F64) -> Rational {
-    rug::Rational::from_f64(d).unwrap().into()
+pub extern "C" fn rational_from_f32(d: F32) -> Rational {
+    rug::Rational::from_f32(d).unwrap().into()
+}
+
+/// Parse f64 from string
+///
+/// # PPL
+/// ```no_run
+/// /// Parse `F64` from `String`.
+/// /// Accepts `inf`, `-inf` and `NaN` too
+/// @mangle_as("f64_from_string")
+/// fn F64 from <str: &String> -> F64
+/// ```
+#[no_mangle]
+pub extern "C" fn f64_from_string(str: &String) -> F64 {
+    let str = str.as_ref();
+    str.trim()
+        .parse::<F64>()
+        .unwrap_or_else(|_| panic!("`{str}` is not a valid F64"))
+}
+
+/// Tangent of angle in radians
+///
+/// # PPL
+/// ```no_run
+/// @mangle_as("tan_f64")
+/// fn tan <:F64> -> F64
+/// ```
+#[no_mangle]
+pub extern "C" fn tan_f64(x: F64) -> F64 {
+    x.tan()
+}
+
+/// Arcsine in radians
+///
+/// # PPL
+/// ```no_run
+/// @mangle_as("asin_f64")
+/// fn asin <:F64> -> F64
+/// ```
+#[no_mangle]
+pub extern "C" fn asin_f64(x: F64) -> F64 {
+    x.asin()
+}
+
+/// Arccosine in radians
+///
+/// # PPL
+/// ```no_run
+/// @mangle_as("acos_f64")
+/// fn acos <:F64> -> F64
+/// ```
+#[no_mangle]
+pub extern "C" fn acos_f64(x: F64) -> F64 {
+    x.acos()
+}
+
+/// Arctangent in radians
+///
+/// # PPL
+/// ```no_run
+/// @mangle_as("atan_f64")
+/// fn atan <:F64> -> F64
+/// ```
+#[no_mangle]
+pub extern "C" fn atan_f64(x: F64) -> F64 {
+    x.atan()
+}
+
+/// Angle in radians between positive x axis and point `(x, y)`
+///
+/// # PPL
+/// ```no_run
+/// @mangle_as("atan2_f64")
+/// fn atan2 <y: F64> <x: F64> -> F64
+/// ```
+#[no_mangle]
+pub extern "C" fn atan2_f64(y: F64, x: F64) -> F64 {
+    y.atan2(x)
 }
@@ -0,0 +1,530 @@
+//! File system operations.
+//! PPL declarations are in `ppl/src/fs.ppl`.
+//!
+//! Operations don't panic on failure.
+//! Instead they return whether they succeeded
+//! and store description of error in `error` out-parameter.
+//! PPL wraps both into `Result`
+
+use std::{
+    fs::{self, OpenOptions},
+    io::{BufReader, Read, Write},
+    path::Path,
+};
+
+use crate::{Array, Integer, String};
+
+/// Store description of error in `error`, if operation failed.
+/// Returns `true` on success
+fn succeeded<T>(result: std::io::Result<T>, error: &mut String) -> bool {
+    match result {
+        Ok(_) => true,
+        Err(err) => {
+            *error = err.to_string().into();
+            false
+        }
+    }
+}
+
+/// Read the whole file into `contents`
+///
+/// # PPL
+/// ```no_run
+/// @mangle_as("read_file_into_string")
+/// fn read file <path: &String> into <contents: &mut String> reporting <error: &mut String> -> Bool
+/// ```
+#[no_mangle]
+pub extern "C" fn read_file_into_string(
+    path: &String,
+    contents: &mut String,
+    error: &mut String,
+) -> bool {
+    succeeded(
+        fs::read_to_string(path.as_ref()).map(|str| *contents = str.into()),
+        error,
+    )
+}
+
+/// Create or truncate file and write `contents` to it
+///
+/// # PPL
+/// ```no_run
+/// @mangle_as("write_string_to_file")
+/// fn write <contents: &String> to file <path: &String> reporting <error: &mut String> -> Bool
+/// ```
+#[no_mangle]
+pub extern "C" fn write_string_to_file(
+    contents: &String,
+    path: &String,
+    error: &mut String,
+) -> bool {
+    succeeded(fs::write(path.as_ref(), contents.as_ref()), error)
+}
+
+/// Append `contents` to the end of file, creating it if needed
+///
+/// # PPL
+/// ```no_run
+/// @mangle_as("append_string_to_file")
+/// fn append <contents: &String> to file <path: &String> reporting <error: &mut String> -> Bool
+/// ```
+#[no_mangle]
+pub extern "C" fn append_string_to_file(
+    contents: &String,
+    path: &String,
+    error: &mut String,
+) -> bool {
+    succeeded(
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path.as_ref())
+            .and_then(|mut file| file.write_all(contents.as_ref().as_bytes())),
+        error,
+    )
+}
+
+/// File, opened by PPL code.
+/// Wrapper around pointer to buffered [`std::fs::File`].
+/// Pointer is null, if file is closed
+///
+/// # PPL
+/// ```no_run
+/// type FileImpl
+///
+/// /// File, opened for reading or writing.
+/// /// It's closed, when destroyed
+/// type File:
+///     impl: Reference<FileImpl>
+/// ```
+#[repr(C)]
+pub struct File {
+    pub data: *mut BufReader<fs::File>,
+}
+
+impl File {
+    /// Get the inner file, if it's open
+    fn as_mut(&mut self) -> std::io::Result<&mut BufReader<fs::File>> {
+        unsafe { self.data.as_mut() }.ok_or_else(|| std::io::Error::other("File is closed"))
+    }
+}
+
+impl From<fs::File> for File {
+    fn from(file: fs::File) -> Self {
+        Self {
+            data: Box::into_raw(Box::new(BufReader::new(file))),
+        }
+    }
+}
+
+/// # PPL
+/// ```no_run
+/// /// File, that isn't open
+/// @mangle_as("closed_file")
+/// fn closed file -> File
+/// ```
+#[no_mangle]
+pub extern "C" fn closed_file() -> File {
+    File {
+        data: std::ptr::null_mut(),
+    }
+}
+
+/// Open file with `options` and store it in `file`
+fn open(path: &String, options: &OpenOptions, file: &mut File, error: &mut String) -> bool {
+    succeeded(
+        options.open(path.as_ref()).map(|opened| {
+            close_file(file);
+            *file = opened.into();
+        }),
+        error,
+    )
+}
+
+/// Open existing file for reading
+///
+/// # PPL
+/// ```no_run
+/// @mangle_as("open_file_for_reading")
+/// fn open <path: &String> for reading into <handle: &mut File> reporting <error: &mut String> -> Bool
+/// ```
+#[no_mangle]
+pub extern "C" fn open_file_for_reading(
+    path: &String,
+    file: &mut File,
+    error: &mut String,
+) -> bool {
+    open(path, OpenOptions::new().read(true), file, error)
+}
+
+/// Create or truncate file and open it for writing
+///
+/// # PPL
+/// ```no_run
+/// @mangle_as("open_file_for_writing")
+/// fn open <path: &String> for writing into <handle: &mut File> reporting <error: &mut String> -> Bool
+/// ```
+#[no_mangle]
+pub extern "C" fn open_file_for_writing(
+    path: &String,
+    file: &mut File,
+    error: &mut String,
+) -> bool {
+    let options = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .clone();
+    open(path, &options, file, error)
+}
+
+/// Open file for writing to its end, creating it if needed
+///
+/// # PPL
+/// ```no_run
+/// @mangle_as("open_file_for_appending")
+/// fn open <path: &String> for appending into <handle: &mut File> reporting <error: &mut String> -> Bool
+/// ```
+#[no_mangle]
+pub extern "C" fn open_file_for_appending(
+    path: &String,
+    file: &mut File,
+    error: &mut String,
+) -> bool {
+    open(
+        path,
+        OpenOptions::new().create(true).append(true),
+        file,
+        error,
+    )
+}
+
+/// Read the rest of open file into `contents`
+///
+/// # PPL
+/// ```no_run
+/// @mangle_as("read_open_file_into_string")
+/// fn read <handle: &mut File> into <contents: &mut String> reporting <error: &mut String> -> Bool
+/// ```
+#[no_mangle]
+pub extern "C" fn read_open_file_into_string(
+    file: &mut File,
+    contents: &mut String,
+    error: &mut String,
+) -> bool {
+    let mut str = std::string::String::new();
+    succeeded(
+        file.as_mut()
+            .and_then(|file| file.read_to_string(&mut str))
+            .map(|_| *contents = str.into()),
+        error,
+    )
+}
+
+/// Write `contents` to open file
+///
+/// # PPL
+/// ```no_run
+/// @mangle_as("write_string_to_open_file")
+/// fn write <contents: &String> to <handle: &mut File> reporting <error: &mut String> -> Bool
+/// ```
+#[no_mangle]
+pub extern "C" fn write_string_to_open_file(
+    contents: &String,
+    file: &mut File,
+    error: &mut String,
+) -> bool {
+    succeeded(
+        file.as_mut()
+            .and_then(|file| file.get_mut().write_all(contents.as_ref().as_bytes())),
+        error,
+    )
+}
+
+/// Open the same file once more.
+/// Result is closed, if it can't be done
+///
+/// # PPL
+/// ```no_run
+/// @mangle_as("clone_file")
+/// fn clone <:&File> -> File
+/// ```
+#[no_mangle]
+pub extern "C" fn clone_file(file: &File) -> File {
+    unsafe { file.data.as_ref() }
+        .and_then(|file| file.get_ref().try_clone().ok())
+        .map_or_else(closed_file, File::from)
+}
+
+/// Close file. Does nothing, if it's already closed
+///
+/// # PPL
+/// ```no_run
+/// @mangle_as("close_file")
+/// fn destroy <:&mut File>
+/// ```
+#[no_mangle]
+pub extern "C" fn close_file(file: &mut File) {
+    if !file.data.is_null() {
+        let _ = unsafe { Box::from_raw(file.data) };
+        file.data = std::ptr::null_mut();
+    }
+}
+
+/// Append sorted names of directory entries to `names`
+///
+/// # PPL
+/// ```no_run
+/// @mangle_as("read_directory_into_array")
+/// fn read directory <path: &String> into <names: &mut Array<String>> reporting <error: &mut String> -> Bool
+/// ```
+#[no_mangle]
+pub extern "C" fn read_directory_into_array(
+    path: &String,
+    names: &mut Array<String>,
+    error: &mut String,
+) -> bool {
+    let entries = fs::read_dir(path.as_ref()).and_then(|dir| {
+        dir.map(|entry| entry.map(|entry| entry.file_name().to_string_lossy().into_owned()))
+            .collect::<std::io::Result<Vec<_>>>()
+    });
+    succeeded(
+        entries.map(|mut entries| {
+            entries.sort();
+            names.extend(entries.into_iter().map(String::from));
+        }),
+        error,
+    )
+}
+
+/// Create directory and all its missing parents
+///
+/// # PPL
+/// ```no_run
+/// @mangle_as("create_directory")
+/// fn create directory <path: &String> reporting <error: &mut String> -> Bool
+/// ```
+#[no_mangle]
+pub extern "C" fn create_directory(path: &String, error: &mut String) -> bool {
+    succeeded(fs::create_dir_all(path.as_ref()), error)
+}
+
+/// Remove empty directory
+///
+/// # PPL
+/// ```no_run
+/// @mangle_as("remove_directory")
+/// fn remove directory <path: &String> reporting <error: &mut String> -> Bool
+/// ```
+#[no_mangle]
+pub extern "C" fn remove_directory(path: &String, error: &mut String) -> bool {
+    succeeded(fs::remove_dir(path.as_ref()), error)
+}
+
+/// # PPL
+/// ```no_run
+/// @mangle_as("remove_file")
+/// fn remove file <path: &String> reporting <error: &mut String> -> Bool
+/// ```
+#[no_mangle]
+pub extern "C" fn remove_file(path: &String, error: &mut String) -> bool {
+    succeeded(fs::remove_file(path.as_ref()), error)
+}
+
+/// Get metadata of file or directory
+///
+/// # PPL
+/// ```no_run
+/// @mangle_as("metadata_of")
+/// fn metadata of <path: &String> into <size: &mut Integer> <is_file: &mut Bool> <is_directory: &mut Bool> <is_readonly: &mut Bool> reporting <error: &mut String> -> Bool
+/// ```
+#[no_mangle]
+pub extern "C" fn metadata_of(
+    path: &String,
+    size: &mut Integer,
+    is_file: &mut bool,
+    is_directory: &mut bool,
+    is_readonly: &mut bool,
+    error: &mut String,
+) -> bool {
+    succeeded(
+        fs::metadata(path.as_ref()).map(|metadata| {
+            *size = metadata.len().into();
+            *is_file = metadata.is_file();
+            *is_directory = metadata.is_dir();
+            *is_readonly = metadata.permissions().readonly();
+        }),
+        error,
+    )
+}
+
+/// # PPL
+/// ```no_run
+/// @mangle_as("path_exists")
+/// fn path <path: &String> exists -> Bool
+/// ```
+#[no_mangle]
+pub extern "C" fn path_exists(path: &String) -> bool {
+    Path::new(path.as_ref()).exists()
+}
+
+/// # PPL
+/// ```no_run
+/// @mangle_as("path_join")
+/// fn join <path: &String> with <name: &String> -> String
+/// ```
+#[no_mangle]
+pub extern "C" fn path_join(path: &String, name: &String) -> String {
+    Path::new(path.as_ref())
+        .join(name.as_ref())
+        .to_string_lossy()
+        .into_owned()
+        .into()
+}
+
+/// Get extension of path without leading dot.
+/// Returns empty string, if there is no extension
+///
+/// # PPL
+/// ```no_run
+/// @mangle_as("extension_of_path")
+/// fn extension of <path: &String> -> String
+/// ```
+#[no_mangle]
+pub extern "C" fn extension_of_path(path: &String) -> String {
+    Path::new(path.as_ref())
+        .extension()
+        .map(|ext| ext.to_string_lossy().into_owned())
+        .unwrap_or_default()
+        .into()
+}
+
+/// Get last component of path.
+/// Returns empty string, if there is no such component
+///
+/// # PPL
+/// ```no_run
+/// @mangle_as("file_name_of_path")
+/// fn file name of <path: &String> -> String
+/// ```
+#[no_mangle]
+pub extern "C" fn file_name_of_path(path: &String) -> String {
+    Path::new(path.as_ref())
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+        .into()
+}
+
+/// Get path without its last component.
+/// Returns empty string, if there is no parent
+///
+/// # PPL
+/// ```no_run
+/// @mangle_as("parent_of_path")
+/// fn parent of <path: &String> -> String
+/// ```
+#[no_mangle]
+pub extern "C" fn parent_of_path(path: &String) -> String {
+    Path::new(path.as_ref())
+        .parent()
+        .map(|parent| parent.to_string_lossy().into_owned())
+        .unwrap_or_default()
+        .into()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn paths() {
+        let path: String = "dir/file.txt".into();
+        assert_eq!(extension_of_path(&path).as_ref(), "txt");
+        assert_eq!(file_name_of_path(&path).as_ref(), "file.txt");
+        assert_eq!(parent_of_path(&path).as_ref(), "dir");
+
+        let name: String = "nested".into();
+        let parent = parent_of_path(&path);
+        assert_eq!(path_join(&parent, &name).as_ref(), "dir/nested");
+    }
+
+    #[test]
+    fn errors() {
+        let path: String = "/definitely/missing/file".into();
+        let mut contents: String = "unchanged".into();
+        let mut error: String = "".into();
+        assert!(!read_file_into_string(&path, &mut contents, &mut error));
+        assert!(!error.as_ref().is_empty());
+        assert_eq!(contents.as_ref(), "unchanged");
+
+        let mut file = closed_file();
+        assert!(!open_file_for_reading(&path, &mut file, &mut error));
+        assert!(file.data.is_null());
+        assert!(!read_open_file_into_string(
+            &mut file,
+            &mut contents,
+            &mut error
+        ));
+        assert_eq!(error.as_ref(), "File is closed");
+    }
+
+    #[test]
+    fn directory_names_with_newlines() {
+        let dir = std::env::temp_dir().join(format!("ppl-fs-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("b"), "").unwrap();
+        fs::write(dir.join("a\nc"), "").unwrap();
+
+        let path: String = dir.to_string_lossy().into_owned().into();
+        let mut names = Array {
+            size: 0.into(),
+            capacity: 0.into(),
+            data: std::ptr::null_mut(),
+        };
+        let mut error: String = "".into();
+        let is_ok = read_directory_into_array(&path, &mut names, &mut error);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(is_ok, "{}", error.as_ref());
+        let names: Vec<_> = names.as_slice().iter().map(String::as_ref).collect();
+        assert_eq!(names, ["a\nc", "b"]);
+    }
+
+    #[test]
+    fn file_handles() {
+        let dir = std::env::temp_dir().join(format!("ppl-file-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path: String = dir.join("notes.txt").to_string_lossy().into_owned().into();
+        let mut error: String = "".into();
+
+        let mut file = closed_file();
+        assert!(open_file_for_writing(&path, &mut file, &mut error));
+        assert!(write_string_to_open_file(
+            &"first\n".into(),
+            &mut file,
+            &mut error
+        ));
+        assert!(open_file_for_appending(&path, &mut file, &mut error));
+        assert!(write_string_to_open_file(
+            &"second\n".into(),
+            &mut file,
+            &mut error
+        ));
+
+        let mut contents: String = "".into();
+        assert!(open_file_for_reading(&path, &mut file, &mut error));
+        let mut copy = clone_file(&file);
+        assert!(read_open_file_into_string(
+            &mut copy,
+            &mut contents,
+            &mut error
+        ));
+        close_file(&mut copy);
+        close_file(&mut file);
+        close_file(&mut file);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(contents.as_ref(), "first\nsecond\n");
+    }
+}
@@ -0,0 +1,93 @@
+//! Hashing of builtin types.
+//! PPL declarations are in `ppl/src/hash.ppl`
+
+use std::hash::{Hash, Hasher};
+
+use crate::{Integer, Rational, String};
+
+/// 64-bit FNV-1a hasher.
+/// Unlike [`DefaultHasher`](std::collections::hash_map::DefaultHasher),
+/// its algorithm is fixed, so hashes are the same in every run
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(0xcbf29ce484222325)
+    }
+}
+
+impl Hasher for Fnv1a {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+    }
+}
+
+/// Hash value with [`Fnv1a`].
+/// Result is non-negative and small enough to be stored inline in [`Integer`]
+fn hash_value(value: &impl Hash) -> Integer {
+    let mut hasher = Fnv1a::default();
+    value.hash(&mut hasher);
+    crate::integer_from_u64(hasher.finish() >> 2)
+}
+
+/// # PPL
+/// ```no_run
+/// @mangle_as("hash_integer")
+/// fn hash of <:&Integer> -> Integer
+/// ```
+#[no_mangle]
+pub extern "C" fn hash_integer(x: &Integer) -> Integer {
+    hash_value(&x.as_ref())
+}
+
+/// # PPL
+/// ```no_run
+/// @mangle_as("hash_rational")
+/// fn hash of <:&Rational> -> Integer
+/// ```
+#[no_mangle]
+pub extern "C" fn hash_rational(x: &Rational) -> Integer {
+    hash_value(x.as_ref())
+}
+
+/// # PPL
+/// ```no_run
+/// @mangle_as("hash_string")
+/// fn hash of <:&String> -> Integer
+/// ```
+#[no_mangle]
+pub extern "C" fn hash_string(x: &String) -> Integer {
+    hash_value(x.as_ref())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn fnv1a() {
+        let mut hasher = Fnv1a::default();
+        hasher.write(b"a");
+        assert_eq!(hasher.finish(), 0xaf63dc4c8601ec8c);
+    }
+
+    #[test]
+    fn small_and_big_integers() {
+        let small = crate::integer_from_i64(-42);
+        assert_eq!(
+            hash_integer(&small).as_ref(),
+            hash_integer(&crate::integer_from_i64(-42)).as_ref()
+        );
+
+        let big: Integer = rug::Integer::from(u64::MAX).into();
+        let hash = hash_integer(&big);
+        assert!(hash.small().is_some_and(|hash| hash >= 0));
+    }
+}
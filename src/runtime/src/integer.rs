@@ -1,11 +1,18 @@
-use std::ffi::c_char;
+use std::{borrow::Cow, ffi::c_char};
 
 use rug::ops::Pow;
 
 use crate::{Rational, String};
 
 /// Big integer number.
-/// Wrapper around pointer to [`rug::Integer`].
+/// Tagged pointer to [`rug::Integer`].
+///
+/// Integers from [`Integer::SMALL_MIN`] to [`Integer::SMALL_MAX`] are stored inline
+/// as `value << 1 | 1`, so they don't allocate.
+/// Pointers to boxed [`rug::Integer`] are aligned and always have lowest bit unset.
+/// Only integers, that don't fit inline, are boxed,
+/// so small and big integers are never equal.
+/// Compiler relies on this representation to inline arithmetic on small integers.
 ///
 /// # PPL
 /// ```no_run
@@ -22,20 +29,45 @@ pub struct Integer {
 
 impl Clone for Integer {
     fn clone(&self) -> Self {
-        self.as_ref().into()
+        match self.small() {
+            Some(value) => Self::from_small(value),
+            None => self.as_ref().into_owned().into(),
+        }
     }
 }
 
 impl Drop for Integer {
     fn drop(&mut self) {
-        // let _ = unsafe { Box::from_raw(self.data) };
+        destroy_integer(self)
     }
 }
 
 impl Integer {
+    /// Minimal integer, that is stored inline
+    pub const SMALL_MIN: i64 = -(1 << 62);
+    /// Maximal integer, that is stored inline
+    pub const SMALL_MAX: i64 = (1 << 62) - 1;
+
+    /// Store small integer inline
+    fn from_small(value: i64) -> Self {
+        debug_assert!((Self::SMALL_MIN..=Self::SMALL_MAX).contains(&value));
+        Self {
+            data: ((value << 1) | 1) as *mut rug::Integer,
+        }
+    }
+
+    /// Get value of integer, if it's stored inline
+    pub fn small(&self) -> Option<i64> {
+        let bits = self.data as i64;
+        (bits & 1 == 1).then_some(bits >> 1)
+    }
+
     /// Get the inner value
-    pub fn as_ref(&self) -> &rug::Integer {
-        unsafe { &*self.data }
+    pub fn as_ref(&self) -> Cow<'_, rug::Integer> {
+        match self.small() {
+            Some(value) => Cow::Owned(value.into()),
+            None => Cow::Borrowed(unsafe { &*self.data }),
+        }
     }
 }
 
@@ -44,7 +76,15 @@ where
     rug::Integer: From<T>,
 {
     fn from(x: T) -> Self {
-        let this = Box::new(rug::Integer::from(x));
+        let value = rug::Integer::from(x);
+        if let Some(small) = value
+            .to_i64()
+            .filter(|small| (Self::SMALL_MIN..=Self::SMALL_MAX).contains(small))
+        {
+            return Self::from_small(small);
+        }
+
+        let this = Box::new(value);
         Self {
             data: Box::into_raw(this),
         }
@@ -60,6 +100,9 @@ pub extern "C" fn integer_from_i32(value: i32) -> Integer {
 /// Construct [`Integer`] from [`i64`]
 #[no_mangle]
 pub extern "C" fn integer_from_i64(value: i64) -> Integer {
+    if (Integer::SMALL_MIN..=Integer::SMALL_MAX).contains(&value) {
+        return Integer::from_small(value);
+    }
     rug::Integer::from(value).into()
 }
 
@@ -99,7 +142,8 @@ pub extern "C" fn integer_as_string(i: Integer) -> String {
 /// ```
 #[no_mangle]
 pub extern "C" fn minus_integer(i: Integer) -> Integer {
-    (-i.as_ref()).into()
+    let i = i.as_ref();
+    (-&*i).into()
 }
 
 /// Add 2 integers
@@ -113,7 +157,21 @@ pub extern "C" fn integer_plus_integer(x: Integer, y: Integer) -> Integer {
     let x = x.as_ref();
     let y = y.as_ref();
 
-    (x + y).into()
+    (&*x + &*y).into()
+}
+
+/// Subtract one integer from another
+///
+/// # PPL
+/// ```no_run
+/// fn <:Integer> - <:Integer> -> Integer
+/// ```
+#[no_mangle]
+pub extern "C" fn integer_minus_integer(x: Integer, y: Integer) -> Integer {
+    let x = x.as_ref();
+    let y = y.as_ref();
+
+    (&*x - &*y).into()
 }
 
 /// Multiply 2 integers
@@ -127,7 +185,7 @@ pub extern "C" fn integer_star_integer(x: Integer, y: Integer) -> Integer {
     let x = x.as_ref();
     let y = y.as_ref();
 
-    (x * y).into()
+    (&*x * &*y).into()
 }
 
 /// Divide 2 integers
@@ -141,7 +199,7 @@ pub extern "C" fn integer_slash_integer(x: Integer, y: Integer) -> Rational {
     let x = x.as_ref();
     let y = y.as_ref();
 
-    (rug::Rational::from(x) / y).into()
+    (rug::Rational::from(&*x) / &*y).into()
 }
 
 /// Compare 2 integers for equality
@@ -180,7 +238,7 @@ pub extern "C" fn integer_less_integer(x: Integer, y: Integer) -> bool {
 /// ```
 #[no_mangle]
 pub extern "C" fn sqrt_integer(i: Integer) -> Integer {
-    i.as_ref().clone().root(2).into()
+    i.as_ref().into_owned().root(2).into()
 }
 
 /// Calculate `x` in `n`th power
@@ -195,7 +253,7 @@ pub extern "C" fn integer_power_integer(x: Integer, n: Integer) -> Integer {
     let n = n.as_ref();
 
     // TODO: support other powers
-    let res: rug::Integer = x.pow(n.to_u32().unwrap()).into();
+    let res: rug::Integer = (&*x).pow(n.to_u32().unwrap()).into();
 
     res.into()
 }
@@ -209,7 +267,7 @@ pub extern "C" fn integer_mod_integer(x: Integer, y: Integer) -> Integer {
     let x = x.as_ref();
     let y = y.as_ref();
 
-    let res = x.clone().modulo(y);
+    let res = x.into_owned().modulo(&y);
     res.into()
 }
 
@@ -219,7 +277,14 @@ pub extern "C" fn integer_mod_integer(x: Integer, y: Integer) -> Integer {
 /// ```
 #[no_mangle]
 pub extern "C" fn destroy_integer(x: &mut Integer) {
-    let _ = unsafe { Box::from_raw(x.data) };
+    if x.small().is_some() {
+        return;
+    }
+
+    let data = std::mem::replace(&mut x.data, std::ptr::null_mut());
+    if !data.is_null() {
+        let _ = unsafe { Box::from_raw(data) };
+    }
 }
 
 /// # PPL
@@ -234,54 +299,40 @@ pub extern "C" fn clone_integer(x: &Integer) -> Integer {
 
 /// # PPL
 /// ```no_run
-/// fn - <:I32> -> I32
+/// /// Parse `Integer` from `String`.
+/// /// Panics, if it's not an integer
+/// @mangle_as("integer_from_string")
+/// fn Integer from <str: &String> -> Integer
 /// ```
 #[no_mangle]
-pub extern "C" fn minus_i32(x: i32) -> i32 {
-    -x
+pub extern "C" fn integer_from_string(str: &String) -> Integer {
+    let str = str.as_ref();
+    str.parse::<rug::Integer>()
+        .unwrap_or_else(|_| panic!("`{str}` is not a valid Integer"))
+        .into()
 }
 
-/// # PPL
-/// ```no_run
-/// fn <:I32> + <:I32> -> I32
-/// ```
-#[no_mangle]
-pub extern "C" fn i32_plus_i32(x: i32, y: i32) -> i32 {
-    x + y
-}
+#[cfg(test)]
+mod test {
+    #[test]
+    fn small_integers() {
+        use super::*;
 
-/// # PPL
-/// ```no_run
-/// @mangle_as("i32_as_string")
-/// fn String from <:I32> -> String
-/// ```
-#[no_mangle]
-pub extern "C" fn i32_as_string(x: i32) -> String {
-    x.to_string().into()
-}
+        let small = integer_from_i64(Integer::SMALL_MAX);
+        assert_eq!(small.small(), Some(Integer::SMALL_MAX));
+        assert_eq!(small.clone().small(), Some(Integer::SMALL_MAX));
 
-/// # PPL
-/// ```no_run
-/// /// Convert `Integer` to `I32
-/// @mangle_as("integer_as_i32")
-/// fn <:Integer> as I32 -> I32
-/// ```
-#[no_mangle]
-pub extern "C" fn integer_as_i32(x: Integer) -> i32 {
-    let integer = x.as_ref();
-    integer
-        .to_i32()
-        .expect(&format!("Integer `{integer}` is too big to fit into i32"))
-}
+        let big = integer_plus_integer(small.clone(), integer_from_i64(1));
+        assert_eq!(big.small(), None);
+        assert_eq!(*big.as_ref(), rug::Integer::from(Integer::SMALL_MAX) + 1);
 
-/// # PPL
-/// ```no_run
-/// /// Parse `Integer` from `String`
-/// @mangle_as("integer_from_string")
-/// fn Integer from <str: &String> -> Integer
-/// ```
-#[no_mangle]
-pub extern "C" fn integer_from_string(str: &String) -> Integer {
-    let str = str.as_ref();
-    str.parse::<rug::Integer>().unwrap().into()
+        let back = integer_minus_integer(big.clone(), integer_from_i64(1));
+        assert_eq!(back.small(), Some(Integer::SMALL_MAX));
+        assert!(integer_eq_integer(back, small.clone()));
+        assert!(integer_less_integer(small, big));
+
+        let min = integer_from_i64(Integer::SMALL_MIN);
+        assert_eq!(min.small(), Some(Integer::SMALL_MIN));
+        assert_eq!(integer_from_i64(Integer::SMALL_MIN - 1).small(), None);
+    }
 }
@@ -0,0 +1,121 @@
+//! Reading input from stdin.
+//! PPL declarations are in `ppl/src/io.ppl`
+
+use std::io::{BufRead, Read};
+
+use crate::{Integer, Rational, String};
+
+/// Read line from stdin, including the newline character.
+/// Returns empty string at the end of input
+///
+/// # PPL
+/// ```no_run
+/// @mangle_as("read_raw_line")
+/// fn read raw line -> String
+/// ```
+#[no_mangle]
+pub extern "C" fn read_raw_line() -> String {
+    let mut line = std::string::String::new();
+    std::io::stdin()
+        .lock()
+        .read_line(&mut line)
+        .expect("failed to read line from stdin");
+    line.into()
+}
+
+/// Read everything from stdin till the end of input
+///
+/// # PPL
+/// ```no_run
+/// @mangle_as("read_all_input")
+/// fn read all input -> String
+/// ```
+#[no_mangle]
+pub extern "C" fn read_all_input() -> String {
+    let mut input = std::string::String::new();
+    std::io::stdin()
+        .lock()
+        .read_to_string(&mut input)
+        .expect("failed to read stdin");
+    input.into()
+}
+
+/// Check that string, ignoring surrounding whitespace, is an integer
+///
+/// # PPL
+/// ```no_run
+/// @mangle_as("string_is_integer")
+/// fn <:&String> is integer -> Bool
+/// ```
+#[no_mangle]
+pub extern "C" fn string_is_integer(str: &String) -> bool {
+    str.as_ref().trim().parse::<rug::Integer>().is_ok()
+}
+
+/// Check that string, ignoring surrounding whitespace,
+/// is a rational in decimal (`-1.5`) or fraction (`1/3`) notation
+///
+/// # PPL
+/// ```no_run
+/// @mangle_as("string_is_rational")
+/// fn <:&String> is rational -> Bool
+/// ```
+#[no_mangle]
+pub extern "C" fn string_is_rational(str: &String) -> bool {
+    crate::rational::parse_rational(str.as_ref()).is_some()
+}
+
+/// Parse integer from string, ignoring surrounding whitespace.
+/// Returns `false` and leaves `value` unchanged, if it's not an integer
+///
+/// # PPL
+/// ```no_run
+/// @mangle_as("parse_integer_from_string")
+/// fn parse integer from <str: &String> into <value: &mut Integer> -> Bool
+/// ```
+#[no_mangle]
+pub extern "C" fn parse_integer_from_string(str: &String, value: &mut Integer) -> bool {
+    match str.as_ref().trim().parse::<rug::Integer>() {
+        Ok(integer) => {
+            *value = integer.into();
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+/// Parse rational from string in decimal (`-1.5`) or fraction (`1/3`) notation,
+/// ignoring surrounding whitespace.
+/// Returns `false` and leaves `value` unchanged, if it's not a rational
+///
+/// # PPL
+/// ```no_run
+/// @mangle_as("parse_rational_from_string")
+/// fn parse rational from <str: &String> into <value: &mut Rational> -> Bool
+/// ```
+#[no_mangle]
+pub extern "C" fn parse_rational_from_string(str: &String, value: &mut Rational) -> bool {
+    match crate::rational::parse_rational(str.as_ref()) {
+        Some(rational) => {
+            *value = rational.into();
+            true
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parsing() {
+        let mut value = crate::integer_from_i64(0);
+        assert!(parse_integer_from_string(&" 42\n".into(), &mut value));
+        assert_eq!(value.small(), Some(42));
+
+        assert!(!parse_integer_from_string(&"4 2".into(), &mut value));
+        assert!(!parse_integer_from_string(&"".into(), &mut value));
+        assert_eq!(value.small(), Some(42));
+    }
+}
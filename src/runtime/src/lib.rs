@@ -1,9 +1,35 @@
+//! Runtime library of PPL.
+//!
+//! # Ownership
+//! [`String`], [`Integer`] and [`Rational`] own the value they point to:
+//! - parameters, taken by value, are owned by the callee and dropped by it
+//! - parameters, taken by reference, are borrowed from the caller
+//! - returned values are owned by the caller, that must `destroy` them
+//!
+//! PPL code clones values before passing them by value,
+//! and calls `destroy` for owned values, when they are no longer needed.
+
+mod array;
+pub use array::*;
+
 mod r#f64;
 pub use r#f64::*;
 
+mod fs;
+pub use fs::*;
+
+mod hash;
+pub use hash::*;
+
 mod integer;
 pub use integer::*;
 
+mod io;
+pub use io::*;
+
+mod numeric;
+pub use numeric::*;
+
 mod rational;
 pub use rational::*;
 
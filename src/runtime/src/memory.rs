@@ -1,10 +1,24 @@
 use libc::{c_void, malloc, memcpy, size_t};
 
-use crate::{integer_from_i64, integer_from_u64, Integer, String, Type};
+use crate::{Integer, String};
 
+/// Address of memory, allocated by PPL code.
+///
+/// Reading memory at address, getting address of a reference and adding offsets to address
+/// are compiler intrinsics, so they have no runtime functions.
+///
+/// # PPL
+/// ```no_run
+/// type MemoryAddressImpl
+///
+/// @builtin
+/// type MemoryAddress:
+///     impl: Reference<MemoryAddressImpl>
+/// ```
 #[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct MemoryAddress {
-    pub value: Integer,
+    pub ptr: *mut c_void,
 }
 
 /// # PPL
@@ -13,83 +27,47 @@ pub struct MemoryAddress {
 /// ```
 #[no_mangle]
 pub extern "C" fn memory_address_as_string(address: MemoryAddress) -> String {
-    let value = address.value.as_ref();
-
-    let hex = format!("0x{}", value.to_string_radix(16).to_uppercase());
-    hex.into()
+    format!("0x{:X}", address.ptr as usize).into()
 }
 
 /// # PPL
 /// ```no_run
-/// fn allocate <n: Integer> bytes -> MemoryAddress
+/// fn <value: Integer> as MemoryAddress -> MemoryAddress
 /// ```
 #[no_mangle]
-pub extern "C" fn allocate_n_bytes(n: Integer) -> MemoryAddress {
-    let n = n.as_ref();
-
-    let n = n.to_usize();
-    if n.is_none() {
-        return MemoryAddress {
-            value: integer_from_i64(0),
-        };
-    }
-    let n = n.unwrap();
-
-    let address = unsafe { malloc(n) } as u64;
+pub extern "C" fn integer_as_memory_address(value: Integer) -> MemoryAddress {
+    let value = value.as_ref();
+    let address = value
+        .to_usize()
+        .unwrap_or_else(|| panic!("Integer `{value}` is not a valid memory address"));
 
     MemoryAddress {
-        value: integer_from_u64(address),
-    }
-}
-
-/// # PPL
-/// ```no_run
-/// fn free <address: &MemoryAddress>
-/// ```
-#[no_mangle]
-pub extern "C" fn free_memory(address: &MemoryAddress) {
-    let address = address.value.as_ref();
-
-    let address = address.to_u64();
-    if address.is_none() {
-        return;
-    }
-    let address = address.unwrap();
-
-    unsafe {
-        libc::free(address as *mut libc::c_void);
+        ptr: address as *mut c_void,
     }
 }
 
 /// # PPL
 /// ```no_run
-/// fn<T> <ty: Type<T>> at <address: &MemoryAddress> -> Reference<T>
+/// fn allocate <n: Integer> bytes -> MemoryAddress
 /// ```
 #[no_mangle]
-pub extern "C" fn read_memory(_ty: Type, address: &MemoryAddress) -> *mut c_void {
-    read_memory_impl(address)
-}
-
-fn read_memory_impl(address: &MemoryAddress) -> *mut c_void {
-    let address = address.value.as_ref();
-
-    let address = address.to_u64().unwrap();
+pub extern "C" fn allocate_n_bytes(n: Integer) -> MemoryAddress {
+    let ptr = match n.as_ref().to_usize() {
+        Some(n) => unsafe { malloc(n) },
+        None => std::ptr::null_mut(),
+    };
 
-    address as *mut libc::c_void
+    MemoryAddress { ptr }
 }
 
 /// # PPL
 /// ```no_run
-/// /// Get memory address of a reference
-/// @mangle_as("address_of")
-/// fn<T> address of <ref: &T> -> MemoryAddress
+/// fn free <address: &MemoryAddress>
 /// ```
 #[no_mangle]
-pub extern "C" fn address_of(ptr: *const c_void) -> MemoryAddress {
-    let address = ptr as usize;
-
-    MemoryAddress {
-        value: integer_from_u64(address as u64),
+pub extern "C" fn free_memory(address: &MemoryAddress) {
+    unsafe {
+        libc::free(address.ptr);
     }
 }
 
@@ -101,8 +79,6 @@ pub extern "C" fn address_of(ptr: *const c_void) -> MemoryAddress {
 /// ```
 #[no_mangle]
 pub extern "C" fn copy_bytes(n: &Integer, src: &MemoryAddress, dst: &MemoryAddress) {
-    let dest = read_memory_impl(dst);
-    let src = read_memory_impl(src);
     let n = n.as_ref().to_usize().unwrap() as size_t;
-    unsafe { memcpy(dest, src, n) };
+    unsafe { memcpy(dst.ptr, src.ptr, n) };
 }
@@ -0,0 +1,88 @@
+//! Runtime for fixed-width numbers.
+//!
+//! Arithmetic, comparisons and conversions between fixed-width numbers
+//! are compiler intrinsics, so only conversions to and from
+//! [`Integer`] and [`String`] are implemented here.
+//! Their PPL declarations are in `ppl/src/{i8, u8, ...}.ppl`
+
+use std::ffi::{c_char, CStr};
+
+use crate::{integer_from_i64, integer_from_u64, Integer, String};
+
+/// Panic on overflow in checked arithmetic on fixed-width integers.
+/// Called by compiler with description of operation, like `I8 + I8`
+#[no_mangle]
+pub extern "C" fn arithmetic_overflow(operation: *const c_char) -> ! {
+    let operation = unsafe { CStr::from_ptr(operation) }.to_string_lossy();
+    panic!("Overflow in `{operation}`")
+}
+
+/// Panic on division of fixed-width integers by zero.
+/// Called by compiler
+#[no_mangle]
+pub extern "C" fn division_by_zero() -> ! {
+    panic!("Division by zero")
+}
+
+/// Define runtime functions for fixed-width integer type
+macro_rules! fixed_width_integer {
+    ($ty:ident, $as_string:ident, $as_integer:ident) => {
+        /// Convert number to [`String`]
+        #[no_mangle]
+        pub extern "C" fn $as_string(x: $ty) -> String {
+            x.to_string().into()
+        }
+
+        /// Convert [`Integer`] to number.
+        /// Panics, if it doesn't fit
+        #[no_mangle]
+        pub extern "C" fn $as_integer(x: Integer) -> $ty {
+            let integer = x.as_ref();
+            integer
+                .to_i128()
+                .and_then(|x| $ty::try_from(x).ok())
+                .unwrap_or_else(|| {
+                    panic!("Integer `{integer}` doesn't fit into {}", stringify!($ty))
+                })
+        }
+    };
+}
+
+fixed_width_integer!(i8, i8_as_string, integer_as_i8);
+fixed_width_integer!(i16, i16_as_string, integer_as_i16);
+fixed_width_integer!(i32, i32_as_string, integer_as_i32);
+fixed_width_integer!(i64, i64_as_string, integer_as_i64);
+fixed_width_integer!(u8, u8_as_string, integer_as_u8);
+fixed_width_integer!(u16, u16_as_string, integer_as_u16);
+fixed_width_integer!(u32, u32_as_string, integer_as_u32);
+fixed_width_integer!(u64, u64_as_string, integer_as_u64);
+
+/// Define construction of [`Integer`] from narrow fixed-width integer
+macro_rules! integer_from {
+    ($ty:ident, $from:ident, $via:ident, $wide:ident) => {
+        /// Construct [`Integer`] from number
+        #[no_mangle]
+        pub extern "C" fn $from(x: $ty) -> Integer {
+            $via(x as $wide)
+        }
+    };
+}
+
+integer_from!(i8, integer_from_i8, integer_from_i64, i64);
+integer_from!(i16, integer_from_i16, integer_from_i64, i64);
+integer_from!(u8, integer_from_u8, integer_from_u64, u64);
+integer_from!(u16, integer_from_u16, integer_from_u64, u64);
+integer_from!(u32, integer_from_u32, integer_from_u64, u64);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn integer_conversions() {
+        assert_eq!(integer_as_i8(integer_from_i8(-128)), -128);
+        assert_eq!(integer_as_u64(integer_from_u64(u64::MAX)), u64::MAX);
+        assert_eq!(integer_as_i64(integer_from_i64(i64::MIN)), i64::MIN);
+        assert_eq!(integer_as_u32(integer_from_u32(u32::MAX)), u32::MAX);
+    }
+}
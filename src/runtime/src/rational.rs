@@ -28,7 +28,7 @@ impl Clone for Rational {
 
 impl Drop for Rational {
     fn drop(&mut self) {
-        // let _ = unsafe { Box::from_raw(self.data) };
+        destroy_rational(self)
     }
 }
 
@@ -61,15 +61,55 @@ pub extern "C" fn rational_from_c_string(str: *const c_char) -> Rational {
     str.parse::<rug::Rational>().unwrap().into()
 }
 
+/// Parse rational from decimal (`-1.5`) or fraction (`1/3`) notation
+pub(crate) fn parse_rational(str: &str) -> Option<rug::Rational> {
+    let str = str.trim();
+    let Some((integer, fraction)) = str.split_once('.') else {
+        return str.parse().ok();
+    };
+
+    let digits = format!("{integer}{fraction}").parse::<Integer>().ok()?;
+    let scale = Integer::from(10).pow(fraction.len() as u32);
+    Some((digits, scale).into())
+}
+
 /// # PPL
 /// ```no_run
-/// fn String from <:Rational> -> String
+/// /// Parse `Rational` from `String`, like `-1.5` or `1/3`.
+/// /// Panics, if it's not a rational
+/// @mangle_as("rational_from_string")
+/// fn Rational from <str: &String> -> Rational
 /// ```
 #[no_mangle]
-pub extern "C" fn rational_as_string(r: Rational) -> String {
-    let value = r.as_ref();
+pub extern "C" fn rational_from_string(str: &String) -> Rational {
+    let str = str.as_ref();
+    parse_rational(str)
+        .unwrap_or_else(|| panic!("`{str}` is not a valid Rational"))
+        .into()
+}
 
-    maybe_to_decimal_string(value).into()
+/// # PPL
+/// ```no_run
+/// /// Numerator of rational in lowest terms.
+/// /// It has the same sign as rational
+/// @mangle_as("numerator_of_rational")
+/// fn numerator of <:Rational> -> Integer
+/// ```
+#[no_mangle]
+pub extern "C" fn numerator_of_rational(r: Rational) -> crate::Integer {
+    r.as_ref().numer().clone().into()
+}
+
+/// # PPL
+/// ```no_run
+/// /// Denominator of rational in lowest terms.
+/// /// It's always positive
+/// @mangle_as("denominator_of_rational")
+/// fn denominator of <:Rational> -> Integer
+/// ```
+#[no_mangle]
+pub extern "C" fn denominator_of_rational(r: Rational) -> crate::Integer {
+    r.as_ref().denom().clone().into()
 }
 
 /// Negates rational
@@ -153,13 +193,108 @@ pub extern "C" fn rational_less_rational(x: Rational, y: Rational) -> bool {
     x < y
 }
 
+/// Get remainder of division, that has the same sign as `x`
+///
+/// # PPL
+/// ```no_run
+/// fn <x: Rational> % <y: Rational> -> Rational
+/// ```
+#[no_mangle]
+pub extern "C" fn rational_mod_rational(x: Rational, y: Rational) -> Rational {
+    let x = x.as_ref();
+    let y = y.as_ref();
+
+    let quotient = rug::Rational::from(x / y).trunc();
+    (x - rug::Rational::from(y * quotient)).into()
+}
+
+/// Calculate `x` in `n`th power
+///
+/// # PPL
+/// ```no_run
+/// fn <x: Rational> ^ <n: Integer> -> Rational
+/// ```
+#[no_mangle]
+pub extern "C" fn rational_power_integer(x: Rational, n: crate::Integer) -> Rational {
+    let n = n.as_ref();
+    let n = n
+        .to_i32()
+        .unwrap_or_else(|| panic!("Power `{n}` is too big"));
+
+    x.as_ref().clone().pow(n).into()
+}
+
+/// # PPL
+/// ```no_run
+/// fn abs <:Rational> -> Rational
+/// ```
+#[no_mangle]
+pub extern "C" fn abs_rational(x: Rational) -> Rational {
+    x.as_ref().clone().abs().into()
+}
+
+/// Round rational towards zero
+///
+/// # PPL
+/// ```no_run
+/// @mangle_as("trunc_rational")
+/// fn trunc <:Rational> -> Integer
+/// ```
+#[no_mangle]
+pub extern "C" fn trunc_rational(x: Rational) -> crate::Integer {
+    let x = x.as_ref();
+    Integer::from(x.numer() / x.denom()).into()
+}
+
+/// Round rational towards negative infinity
+///
+/// # PPL
+/// ```no_run
+/// @mangle_as("floor_rational")
+/// fn floor <:Rational> -> Integer
+/// ```
+#[no_mangle]
+pub extern "C" fn floor_rational(x: Rational) -> crate::Integer {
+    let (numer, _) = x.as_ref().clone().floor().into_numer_denom();
+    numer.into()
+}
+
+/// Round rational towards positive infinity
+///
+/// # PPL
+/// ```no_run
+/// @mangle_as("ceil_rational")
+/// fn ceil <:Rational> -> Integer
+/// ```
+#[no_mangle]
+pub extern "C" fn ceil_rational(x: Rational) -> crate::Integer {
+    let (numer, _) = x.as_ref().clone().ceil().into_numer_denom();
+    numer.into()
+}
+
+/// Round rational to the nearest integer, rounding half-way cases away from zero
+///
+/// # PPL
+/// ```no_run
+/// @mangle_as("round_rational")
+/// fn round <:Rational> -> Integer
+/// ```
+#[no_mangle]
+pub extern "C" fn round_rational(x: Rational) -> crate::Integer {
+    let (numer, _) = x.as_ref().clone().round().into_numer_denom();
+    numer.into()
+}
+
 /// # PPL
 /// ```no_run
 /// fn destroy <:&mut Rational>
 /// ```
 #[no_mangle]
 pub extern "C" fn destroy_rational(x: &mut Rational) {
-    let _ = unsafe { Box::from_raw(x.data) };
+    let data = std::mem::replace(&mut x.data, std::ptr::null_mut());
+    if !data.is_null() {
+        let _ = unsafe { Box::from_raw(data) };
+    }
 }
 
 /// # PPL
@@ -185,6 +320,9 @@ pub fn maybe_to_decimal_string(r: &rug::Rational) -> std::string::String {
     numer *= Integer::from(2).pow(pow10 - pow2);
     numer *= Integer::from(5).pow(pow10 - pow5);
 
+    let sign = if numer < 0 { "-" } else { "" };
+    numer.abs_mut();
+
     let pow10 = pow10 as usize;
     let numer = format!("{numer:0>pow10$}");
     let dotpoint = numer.len() - pow10;
@@ -196,7 +334,7 @@ pub fn maybe_to_decimal_string(r: &rug::Rational) -> std::string::String {
     if after_dot.is_empty() {
         after_dot = "0";
     }
-    format!("{before_dot}.{after_dot}")
+    format!("{sign}{before_dot}.{after_dot}")
 }
 
 #[cfg(test)]
@@ -226,5 +364,19 @@ mod test {
 
         let r = rug::Rational::from((1, 16));
         assert_eq!(maybe_to_decimal_string(&r), "0.0625");
+
+        let r = rug::Rational::from((-1, 2));
+        assert_eq!(maybe_to_decimal_string(&r), "-0.5");
+    }
+
+    #[test]
+    fn parse_rational() {
+        use super::parse_rational;
+        use rug::Rational;
+
+        assert_eq!(parse_rational("-1.25"), Some(Rational::from((-5, 4))));
+        assert_eq!(parse_rational(" .5 "), Some(Rational::from((1, 2))));
+        assert_eq!(parse_rational("2/6"), Some(Rational::from((1, 3))));
+        assert_eq!(parse_rational("1.-5"), None);
     }
 }
@@ -1,5 +1,7 @@
 use std::{ffi::c_char, io::Write};
 
+use crate::Array;
+
 /// PPL's String type.
 /// Wrapper around pointer to [`std::string::String`].
 ///
@@ -24,7 +26,7 @@ impl Clone for String {
 
 impl Drop for String {
     fn drop(&mut self) {
-        // let _ = unsafe { Box::from_raw(self.data) };
+        destroy_string(self)
     }
 }
 
@@ -91,7 +93,10 @@ pub extern "C" fn print_string(str: &String) {
 /// ```
 #[no_mangle]
 pub extern "C" fn destroy_string(x: &mut String) {
-    let _ = unsafe { Box::from_raw(x.data) };
+    let data = std::mem::replace(&mut x.data, std::ptr::null_mut());
+    if !data.is_null() {
+        let _ = unsafe { Box::from_raw(data) };
+    }
 }
 
 /// # PPL
@@ -103,3 +108,386 @@ pub extern "C" fn destroy_string(x: &mut String) {
 pub extern "C" fn clone_string(x: &String) -> String {
     x.clone()
 }
+
+/// Unicode scalar value.
+/// Represented as its code point
+///
+/// # PPL
+/// ```no_run
+/// @builtin
+/// type Char
+/// ```
+pub type Char = u32;
+
+/// Convert [`Char`] to Rust's [`char`]
+fn as_char(c: Char) -> char {
+    char::from_u32(c).unwrap_or_else(|| panic!("`{c}` is not a valid unicode scalar value"))
+}
+
+/// Convert [`Integer`](crate::Integer) to index
+fn as_index(i: &crate::Integer) -> usize {
+    let i = i.as_ref();
+    i.to_usize()
+        .unwrap_or_else(|| panic!("`{i}` is not a valid index"))
+}
+
+/// Get byte offset of `i`-th char in string.
+/// Index equal to length of string is allowed
+fn char_offset(str: &str, i: usize) -> usize {
+    str.char_indices()
+        .map(|(offset, _)| offset)
+        .chain(std::iter::once(str.len()))
+        .nth(i)
+        .unwrap_or_else(|| panic!("Index `{i}` is out of bounds for `{str}`"))
+}
+
+/// # PPL
+/// ```no_run
+/// /// Get `Char` by its code point.
+/// /// Panics, if it's not a valid unicode scalar value
+/// @mangle_as("char_from_integer")
+/// fn Char from <code: Integer> -> Char
+/// ```
+#[no_mangle]
+pub extern "C" fn char_from_integer(code: crate::Integer) -> Char {
+    let code = code.as_ref();
+    code.to_u32()
+        .and_then(char::from_u32)
+        .unwrap_or_else(|| panic!("`{code}` is not a valid unicode scalar value")) as Char
+}
+
+/// # PPL
+/// ```no_run
+/// /// Get code point of `Char`
+/// @mangle_as("integer_from_char")
+/// fn Integer from <:Char> -> Integer
+/// ```
+#[no_mangle]
+pub extern "C" fn integer_from_char(c: Char) -> crate::Integer {
+    crate::integer_from_u64(c as u64)
+}
+
+/// # PPL
+/// ```no_run
+/// @mangle_as("char_as_string")
+/// fn String from <:Char> -> String
+/// ```
+#[no_mangle]
+pub extern "C" fn char_as_string(c: Char) -> String {
+    as_char(c).to_string().into()
+}
+
+/// # PPL
+/// ```no_run
+/// @mangle_as("char_eq_char")
+/// fn <:Char> == <:Char> -> Bool
+/// ```
+#[no_mangle]
+pub extern "C" fn char_eq_char(x: Char, y: Char) -> bool {
+    x == y
+}
+
+/// # PPL
+/// ```no_run
+/// @mangle_as("char_less_char")
+/// fn <:Char> < <:Char> -> Bool
+/// ```
+#[no_mangle]
+pub extern "C" fn char_less_char(x: Char, y: Char) -> bool {
+    x < y
+}
+
+/// # PPL
+/// ```no_run
+/// /// Size of UTF-8 encoding of `Char` in bytes
+/// @mangle_as("byte_length_of_char")
+/// fn byte length of <:Char> -> Integer
+/// ```
+#[no_mangle]
+pub extern "C" fn byte_length_of_char(c: Char) -> crate::Integer {
+    crate::integer_from_u64(as_char(c).len_utf8() as u64)
+}
+
+/// # PPL
+/// ```no_run
+/// /// Number of chars in string
+/// @mangle_as("length_of_string")
+/// fn length of <:&String> -> Integer
+/// ```
+#[no_mangle]
+pub extern "C" fn length_of_string(str: &String) -> crate::Integer {
+    crate::integer_from_u64(str.as_ref().chars().count() as u64)
+}
+
+/// # PPL
+/// ```no_run
+/// /// Number of bytes in UTF-8 encoding of string
+/// @mangle_as("byte_length_of_string")
+/// fn byte length of <:&String> -> Integer
+/// ```
+#[no_mangle]
+pub extern "C" fn byte_length_of_string(str: &String) -> crate::Integer {
+    crate::integer_from_u64(str.as_ref().len() as u64)
+}
+
+/// # PPL
+/// ```no_run
+/// /// Get `i`-th char of string
+/// @mangle_as("string_at_integer")
+/// fn <str: &String> [ <i: Integer> ] -> Char
+/// ```
+#[no_mangle]
+pub extern "C" fn string_at_integer(str: &String, i: crate::Integer) -> Char {
+    let str = str.as_ref();
+    let i = as_index(&i);
+    str.chars()
+        .nth(i)
+        .unwrap_or_else(|| panic!("Index `{i}` is out of bounds for `{str}`")) as Char
+}
+
+/// # PPL
+/// ```no_run
+/// /// Get char, that starts at given byte offset
+/// @mangle_as("char_at_byte_of_string")
+/// fn char at byte <offset: Integer> of <str: &String> -> Char
+/// ```
+#[no_mangle]
+pub extern "C" fn char_at_byte_of_string(offset: crate::Integer, str: &String) -> Char {
+    let str = str.as_ref();
+    let offset = as_index(&offset);
+    str.get(offset..)
+        .and_then(|rest| rest.chars().next())
+        .unwrap_or_else(|| panic!("No char starts at byte `{offset}` of `{str}`")) as Char
+}
+
+/// # PPL
+/// ```no_run
+/// /// Get chars of string from `from` up to, but not including, `to`
+/// @mangle_as("string_slice_integer_integer")
+/// fn <str: &String> [ <from: Integer> to <to: Integer> ] -> String
+/// ```
+#[no_mangle]
+pub extern "C" fn string_slice_integer_integer(
+    str: &String,
+    from: crate::Integer,
+    to: crate::Integer,
+) -> String {
+    let str = str.as_ref();
+    let from = as_index(&from);
+    let to = as_index(&to);
+    if from > to {
+        panic!("Invalid range `{from}` to `{to}` for `{str}`");
+    }
+
+    let start = char_offset(str, from);
+    let end = str[start..]
+        .char_indices()
+        .map(|(offset, _)| start + offset)
+        .chain(std::iter::once(str.len()))
+        .nth(to - from)
+        .unwrap_or_else(|| panic!("Index `{to}` is out of bounds for `{str}`"));
+    str[start..end].into()
+}
+
+/// # PPL
+/// ```no_run
+/// /// Split string by separator and append parts to `parts`
+/// @mangle_as("split_string_into_array")
+/// fn split <str: &String> by <separator: &String> into <parts: &mut Array<String>>
+/// ```
+#[no_mangle]
+pub extern "C" fn split_string_into_array(
+    str: &String,
+    separator: &String,
+    parts: &mut Array<String>,
+) {
+    let separator = separator.as_ref();
+    if separator.is_empty() {
+        panic!("Separator must not be empty");
+    }
+    parts.extend(str.as_ref().split(separator.as_str()).map(String::from));
+}
+
+/// # PPL
+/// ```no_run
+/// /// Find index of the first occurrence of `pattern` in string.
+/// /// Returns `-1`, if there is no such occurrence
+/// @mangle_as("find_string_in_string")
+/// fn find <pattern: &String> in <str: &String> -> Integer
+/// ```
+#[no_mangle]
+pub extern "C" fn find_string_in_string(pattern: &String, str: &String) -> crate::Integer {
+    let str = str.as_ref();
+    match str.find(pattern.as_ref().as_str()) {
+        Some(offset) => crate::integer_from_u64(str[..offset].chars().count() as u64),
+        None => crate::integer_from_i64(-1),
+    }
+}
+
+/// # PPL
+/// ```no_run
+/// @mangle_as("string_contains_string")
+/// fn <str: &String> contains <pattern: &String> -> Bool
+/// ```
+#[no_mangle]
+pub extern "C" fn string_contains_string(str: &String, pattern: &String) -> bool {
+    str.as_ref().contains(pattern.as_ref().as_str())
+}
+
+/// # PPL
+/// ```no_run
+/// @mangle_as("string_starts_with_string")
+/// fn <str: &String> starts with <prefix: &String> -> Bool
+/// ```
+#[no_mangle]
+pub extern "C" fn string_starts_with_string(str: &String, prefix: &String) -> bool {
+    str.as_ref().starts_with(prefix.as_ref().as_str())
+}
+
+/// # PPL
+/// ```no_run
+/// @mangle_as("string_ends_with_string")
+/// fn <str: &String> ends with <suffix: &String> -> Bool
+/// ```
+#[no_mangle]
+pub extern "C" fn string_ends_with_string(str: &String, suffix: &String) -> bool {
+    str.as_ref().ends_with(suffix.as_ref().as_str())
+}
+
+/// # PPL
+/// ```no_run
+/// /// Remove leading and trailing whitespace
+/// @mangle_as("trim_string")
+/// fn trim <:&String> -> String
+/// ```
+#[no_mangle]
+pub extern "C" fn trim_string(str: &String) -> String {
+    str.as_ref().trim().into()
+}
+
+/// # PPL
+/// ```no_run
+/// /// Replace all occurrences of `from` with `to`
+/// @mangle_as("replace_string_with_string_in_string")
+/// fn replace <from: &String> with <to: &String> in <str: &String> -> String
+/// ```
+#[no_mangle]
+pub extern "C" fn replace_string_with_string_in_string(
+    from: &String,
+    to: &String,
+    str: &String,
+) -> String {
+    str.as_ref()
+        .replace(from.as_ref().as_str(), to.as_ref())
+        .into()
+}
+
+/// # PPL
+/// ```no_run
+/// @mangle_as("uppercase_string")
+/// fn uppercase <:&String> -> String
+/// ```
+#[no_mangle]
+pub extern "C" fn uppercase_string(str: &String) -> String {
+    str.as_ref().to_uppercase().into()
+}
+
+/// # PPL
+/// ```no_run
+/// @mangle_as("lowercase_string")
+/// fn lowercase <:&String> -> String
+/// ```
+#[no_mangle]
+pub extern "C" fn lowercase_string(str: &String) -> String {
+    str.as_ref().to_lowercase().into()
+}
+
+/// # PPL
+/// ```no_run
+/// @mangle_as("string_eq_string")
+/// fn <:String> == <:String> -> Bool
+/// ```
+#[no_mangle]
+pub extern "C" fn string_eq_string(x: String, y: String) -> bool {
+    x.as_ref() == y.as_ref()
+}
+
+/// Compare strings lexicographically
+///
+/// # PPL
+/// ```no_run
+/// @mangle_as("string_less_string")
+/// fn <:String> < <:String> -> Bool
+/// ```
+#[no_mangle]
+pub extern "C" fn string_less_string(x: String, y: String) -> bool {
+    x.as_ref() < y.as_ref()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn slicing() {
+        let str: String = "привет, мир".into();
+        assert_eq!(length_of_string(&str).as_ref().to_u64(), Some(11));
+        assert_eq!(byte_length_of_string(&str).as_ref().to_u64(), Some(20));
+        assert_eq!(
+            string_at_integer(&str, crate::integer_from_u64(1)),
+            'р' as Char
+        );
+
+        let slice = string_slice_integer_integer(
+            &str,
+            crate::integer_from_u64(8),
+            crate::integer_from_u64(11),
+        );
+        assert_eq!(slice.as_ref(), "мир");
+
+        let str: String = "hi".into();
+        let slice = string_slice_integer_integer(
+            &str,
+            crate::integer_from_u64(1),
+            crate::integer_from_u64(2),
+        );
+        assert_eq!(slice.as_ref(), "i");
+
+        let str: String = "привет, мир".into();
+        let pattern: String = "мир".into();
+        assert_eq!(
+            find_string_in_string(&pattern, &str).as_ref().to_i64(),
+            Some(8)
+        );
+    }
+
+    #[test]
+    fn out_of_bounds_slice_reports_original_range() {
+        let str: String = "привет".into();
+        let result = std::panic::catch_unwind(|| {
+            string_slice_integer_integer(
+                &str,
+                crate::integer_from_u64(2),
+                crate::integer_from_u64(10),
+            )
+        });
+        let message = result.unwrap_err();
+        let message = message.downcast_ref::<std::string::String>().unwrap();
+        assert_eq!(message, "Index `10` is out of bounds for `привет`");
+    }
+
+    #[test]
+    fn splitting() {
+        let str: String = "a, b,, c".into();
+        let separator: String = ", ".into();
+        let mut parts = Array {
+            size: 0.into(),
+            capacity: 0.into(),
+            data: std::ptr::null_mut(),
+        };
+        split_string_into_array(&str, &separator, &mut parts);
+
+        let parts: Vec<_> = parts.as_slice().iter().map(String::as_ref).collect();
+        assert_eq!(parts, ["a", "b,", "c"]);
+    }
+}
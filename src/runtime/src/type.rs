@@ -1,6 +1,9 @@
 use crate::{Integer, String};
 
-/// Runtime type information
+/// Runtime type information.
+///
+/// Values of this type are shared globals of PPL modules and
+/// are passed bitwise, so callees must wrap them in [`ManuallyDrop`](std::mem::ManuallyDrop)
 ///
 /// # PPL
 /// ```no_run
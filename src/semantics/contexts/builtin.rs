@@ -86,6 +86,11 @@ impl BuiltinTypes<'_> {
 
     builtin_types!(none, bool, integer, rational, string, reference, i32, f64);
 
+    /// Get builtin fixed-width number type by literal suffix, like `i8` or `f32`
+    pub fn for_suffix(&self, suffix: &str) -> Type {
+        self.get_type(&suffix.to_uppercase())
+    }
+
     /// Get builtin type for types
     pub fn type_(&self) -> Type {
         self.get_type("Type")
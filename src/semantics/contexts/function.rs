@@ -1,7 +1,7 @@
 use std::fmt::Display;
 
 use crate::{
-    hir::{Class, Function, ParameterOrVariable, Trait, Type, Variable},
+    hir::{Class, Function, Name, ParameterOrVariable, Trait, Type, Variable},
     named::Named,
     semantics::{AddDeclaration, FindDeclaration, FindDeclarationHere},
     DataHolder,
@@ -54,6 +54,30 @@ impl FindDeclarationHere for FunctionContext<'_> {
             .find(|p| p.name() == name)
             .cloned()
     }
+
+    fn variable_names_here(&self) -> Vec<Name> {
+        self.variables
+            .iter()
+            .map(|v| v.name().to_string())
+            .chain(
+                self.function
+                    .read()
+                    .unwrap()
+                    .parameters()
+                    .map(|p| p.name().to_string()),
+            )
+            .collect()
+    }
+
+    fn type_names_here(&self) -> Vec<Name> {
+        self.function
+            .read()
+            .unwrap()
+            .generic_types
+            .iter()
+            .map(|t| t.name().to_string())
+            .collect()
+    }
 }
 
 impl FindDeclaration for FunctionContext<'_> {
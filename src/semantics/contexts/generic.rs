@@ -1,7 +1,7 @@
 use std::{collections::HashMap, fmt::Display};
 
 use crate::{
-    hir::{FunctionData, GenericType, Type, TypeReference, Typed},
+    hir::{FunctionData, GenericType, Name, Type, TypeReference, Typed},
     named::Named,
     semantics::{AddDeclaration, ConvertibleTo, FindDeclaration, FindDeclarationHere},
 };
@@ -115,6 +115,13 @@ impl FindDeclarationHere for GenericContext<'_> {
             .find(|p| p.name() == name)
             .cloned()
     }
+
+    fn type_names_here(&self) -> Vec<Name> {
+        self.generic_parameters
+            .iter()
+            .map(|p| p.name().to_string())
+            .collect()
+    }
 }
 
 impl FindDeclaration for GenericContext<'_> {
@@ -1,7 +1,7 @@
 use std::fmt::Display;
 
 use crate::{
-    hir::{Class, Function, Trait, Type, Variable},
+    hir::{Class, Function, Name, Trait, Type, Variable},
     named::Named,
     semantics::{AddDeclaration, FindDeclaration, FindDeclarationHere},
 };
@@ -56,6 +56,20 @@ impl FindDeclarationHere for TraitContext<'_> {
             .cloned()
             .collect()
     }
+
+    fn type_names_here(&self) -> Vec<Name> {
+        vec!["Self".to_string()]
+    }
+
+    fn function_formats_here(&self) -> Vec<String> {
+        self.tr
+            .read()
+            .unwrap()
+            .functions
+            .values()
+            .map(|f| f.read().unwrap().name_format().to_string())
+            .collect()
+    }
 }
 
 impl FindDeclaration for TraitContext<'_> {
@@ -10,7 +10,7 @@ use crate::{
 };
 
 use super::{
-    error::{CantDeduceReturnType, Error, ReturnTypeMismatch},
+    error::{CantDeduceReturnType, Error, InvalidTest, ReturnTypeMismatch},
     Context, Convert, FunctionContext, GenericContext, Monomorphize, ToHIR, TraitContext,
 };
 
@@ -72,12 +72,32 @@ impl Declare for ast::FunctionDeclaration {
             hir::Annotation::MangleAs(name) => Some(name.clone()),
             _ => None,
         });
+        let is_test = annotations.contains(&hir::Annotation::Test);
+        let has_parameters = name_parts
+            .iter()
+            .any(|part| matches!(part, hir::FunctionNamePart::Parameter(_)));
+        let is_generic = !generic_parameters.is_empty();
+        if is_test && (has_parameters || is_generic) {
+            return Err(InvalidTest {
+                parameters: has_parameters.then(|| self.name_parts.range().into()),
+                generic: is_generic.then(|| {
+                    if self.generic_parameters.is_empty() {
+                        self.name_parts.range().into()
+                    } else {
+                        self.generic_parameters.range().into()
+                    }
+                }),
+            }
+            .into());
+        }
 
         let f = Function::new(
             hir::FunctionData::build(context.compiler().current_module(), self.keyword)
                 .with_generic_types(generic_parameters)
                 .with_name(name_parts)
                 .with_mangled_name(mangled_name)
+                .with_test(is_test)
+                .with_docs(self.docs.clone())
                 .with_return_type(return_type),
         );
 
@@ -183,6 +203,7 @@ impl Declare for ast::TraitDeclaration {
             supertraits,
             functions: IndexMap::new(),
             module: context.compiler().current_module(),
+            docs: self.docs.clone(),
         });
 
         TraitContext::new(tr.clone(), context).run(|context| {
@@ -244,6 +265,7 @@ impl Declare for ast::TypeDeclaration {
             generic_parameters,
             builtin,
             members: vec![],
+            docs: self.docs.clone(),
         });
 
         context.add_type(ty.clone());
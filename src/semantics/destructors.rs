@@ -2,15 +2,66 @@ use log::trace;
 
 use crate::{
     hir::{
-        self, Call, Expression, FunctionData, ParameterOrVariable, Statement, Typed,
-        VariableReference,
+        self, Call, Expression, FunctionData, Generic, ParameterOrVariable, Statement, Typed,
+        Variable, VariableData, VariableReference,
     },
-    syntax::Ranged,
+    mutability::Mutable,
+    syntax::{Identifier, Keyword, Ranged},
     DataHolder,
 };
 
 use super::Context;
 
+/// Add call to destructor of `v`, if its type has one
+fn destroy(statements: &mut Vec<Statement>, v: Expression, context: &mut impl Context) {
+    if let Some(destructor) = context.destructor_for(v.ty()) {
+        statements.push(
+            hir::Expression::from(Call {
+                range: v.range(),
+                function: destructor,
+                generic: None,
+                args: vec![v],
+            })
+            .into(),
+        );
+    }
+}
+
+/// Add call to destructor of variable or parameter
+fn destroy_variable(
+    statements: &mut Vec<Statement>,
+    variable: ParameterOrVariable,
+    context: &mut impl Context,
+) {
+    let span = variable.range();
+    destroy(
+        statements,
+        VariableReference { variable, span }.into(),
+        context,
+    );
+}
+
+/// Variable, that holds value of expression
+fn temporary(prefix: &str, expr: &Expression) -> Variable {
+    let offset = expr.start();
+    Variable::new(VariableData {
+        keyword: Keyword::<"let">::at(offset),
+        mutability: expr.mutability(),
+        name: Identifier::from(format!("${prefix}@{offset}")).at(offset),
+        type_reference: None,
+        ty: expr.ty(),
+        initializer: Some(expr.clone()),
+    })
+}
+
+/// Is expression a new value, that isn't owned by anything else?
+fn is_temporary(expr: &Expression) -> bool {
+    matches!(
+        expr,
+        Expression::Literal(_) | Expression::Call(_) | Expression::Constructor(_)
+    )
+}
+
 /// Insert destructors calls to HIR
 fn with_destructors(
     statements: &[Statement],
@@ -20,20 +71,6 @@ fn with_destructors(
     let mut decls: Vec<ParameterOrVariable> = vec![];
     let mut new_statements = vec![];
 
-    fn destroy(statements: &mut Vec<Statement>, v: Expression, context: &mut impl Context) {
-        if let Some(destructor) = context.destructor_for(v.ty()) {
-            statements.push(
-                hir::Expression::from(Call {
-                    range: v.range(),
-                    function: destructor,
-                    generic: None,
-                    args: vec![v],
-                })
-                .into(),
-            );
-        }
-    }
-
     let mut stmts = vec![];
     for stmt in statements {
         match stmt {
@@ -51,9 +88,23 @@ fn with_destructors(
             Block(_) => {
                 unreachable!("Block should be flattened")
             }
-            Assignment(a) => {
+            Assignment(a) if context.destructor_for(a.target.ty()).is_some() => {
+                // New value may use the old one, so it's computed before destruction
+                let value = temporary("new", &a.value);
+                new_statements.push(hir::Declaration::from(value.clone()).into());
                 destroy(&mut new_statements, a.target.clone(), context);
-                new_statements.push(stmt.clone());
+                let span = a.value.range();
+                new_statements.push(
+                    hir::Assignment {
+                        target: a.target.clone(),
+                        value: VariableReference {
+                            variable: value.into(),
+                            span,
+                        }
+                        .into(),
+                    }
+                    .into(),
+                );
             }
             If(if_stmt) => {
                 new_statements.push(
@@ -113,30 +164,27 @@ fn with_destructors(
                     decls.retain(|decl| decl != variable);
                 }
                 for variable in kill {
-                    let span = variable.range();
-                    destroy(
-                        &mut new_statements,
-                        VariableReference { variable, span }.into(),
-                        context,
-                    );
+                    destroy_variable(&mut new_statements, variable, context);
                 }
                 decls = vec![];
                 new_statements.push(stmt.clone());
                 break;
             }
-            Expression(_) | Use(_) | Declaration(_) => {
+            Expression(expr)
+                if is_temporary(expr) && context.destructor_for(expr.ty()).is_some() =>
+            {
+                // Unused result is stored to be destroyed right away
+                let tmp = temporary("unused", expr);
+                new_statements.push(hir::Declaration::from(tmp.clone()).into());
+                destroy_variable(&mut new_statements, tmp.into(), context);
+            }
+            Assignment(_) | Expression(_) | Use(_) | Declaration(_) => {
                 new_statements.push(stmt.clone());
             }
         }
     }
     for v in decls {
-        let span = v.range();
-        let variable = v.into();
-        destroy(
-            &mut new_statements,
-            VariableReference { variable, span }.into(),
-            context,
-        );
+        destroy_variable(&mut new_statements, v, context);
     }
     new_statements
 }
@@ -151,19 +199,30 @@ impl InsertDestructors for hir::ModuleData {
     fn insert_destructors(&mut self, context: &mut impl Context) {
         let kill = vec![];
         self.statements = with_destructors(&self.statements, kill, context);
+        for f in &self.monomorphized_functions {
+            f.write().unwrap().insert_destructors(context);
+        }
     }
 }
 
 impl InsertDestructors for FunctionData {
     fn insert_destructors(&mut self, context: &mut impl Context) {
-        if !self.is_definition() {
+        // Destructors of generic values are only known after monomorphization,
+        // so they are inserted in monomorphized versions of function
+        if !self.is_definition() || self.is_generic() {
             return;
         }
 
         trace!(target: "steps", "Inserting destructors in: {self}");
 
-        let kill = self.parameters().map(Into::into).collect();
-        self.body = with_destructors(&self.body, kill, context);
+        let parameters: Vec<ParameterOrVariable> = self.parameters().map(Into::into).collect();
+        self.body = with_destructors(&self.body, parameters.clone(), context);
+        // Parameters are owned by function, even if it doesn't return explicitly
+        if !matches!(self.body.last(), Some(Statement::Return(_))) {
+            for parameter in parameters {
+                destroy_variable(&mut self.body, parameter, context);
+            }
+        }
 
         trace!(target: "steps", "After inserting destructors: {self}");
     }
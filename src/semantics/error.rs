@@ -22,6 +22,10 @@ pub struct UndefinedVariable {
     /// Span of name
     #[label("reference to undefined variable")]
     pub at: SourceSpan,
+
+    /// Suggestion of similar or importable name
+    #[help]
+    pub help: Option<String>,
 }
 
 /// Diagnostic for unknown type
@@ -35,6 +39,10 @@ pub struct UnknownType {
     /// Span of name
     #[label("reference to unknown type")]
     pub at: SourceSpan,
+
+    /// Suggestion of similar or importable name
+    #[help]
+    pub help: Option<String>,
 }
 
 /// Diagnostic for unknown annotations
@@ -50,6 +58,34 @@ pub struct UnknownAnnotation {
     pub at: SourceSpan,
 }
 
+/// Diagnostic for literals, that don't fit into their type
+#[derive(Error, Diagnostic, Debug, Clone, PartialEq)]
+#[error("literal `{value}` is out of range for `{ty}`")]
+#[diagnostic(code(semantics::literal_out_of_range))]
+pub struct LiteralOutOfRange {
+    /// Value of literal
+    pub value: String,
+    /// Type of literal
+    pub ty: Type,
+
+    /// Span of literal
+    #[label("this literal doesn't fit into `{ty}`")]
+    pub at: SourceSpan,
+}
+
+/// Diagnostic for test functions that can't be called by test harness
+#[derive(Error, Diagnostic, Debug, Clone, PartialEq)]
+#[error("test function can't have parameters or generic types")]
+#[diagnostic(code(semantics::invalid_test))]
+pub struct InvalidTest {
+    /// Span of test function's name, if test has parameters
+    #[label("this test has parameters")]
+    pub parameters: Option<SourceSpan>,
+    /// Span of test function's generic parameters, if test is generic
+    #[label("this test is generic")]
+    pub generic: Option<SourceSpan>,
+}
+
 /// Diagnostic for assignment to immutable
 #[derive(Error, Diagnostic, Debug, Clone, PartialEq)]
 #[error("assignment to immutable")]
@@ -170,6 +206,9 @@ pub struct NoFunction {
 
     /// Reasons, why candidates failed
     pub candidates: Vec<CandidateNotViable>,
+
+    /// Suggestion of similar or importable function
+    pub help: Option<String>,
 }
 
 impl Display for NoFunction {
@@ -186,6 +225,12 @@ impl Diagnostic for NoFunction {
         Some(Box::new("semantics::no_function"))
     }
 
+    fn help<'a>(&'a self) -> Option<Box<dyn std::fmt::Display + 'a>> {
+        self.help
+            .as_ref()
+            .map(|help| Box::new(help) as Box<dyn std::fmt::Display>)
+    }
+
     fn labels(&self) -> Option<Box<dyn Iterator<Item = miette::LabeledSpan> + '_>> {
         if self.arguments.is_empty() {
             Some(Box::new(std::iter::once(
@@ -403,6 +448,9 @@ pub struct UnresolvedImport {
     /// Location of the unresolved item
     #[label("No such item im module")]
     pub at: SourceSpan,
+    /// Suggestion of similar item in module
+    #[help]
+    pub help: Option<String>,
 }
 
 /// Helper macro to create error enumeration
@@ -427,6 +475,8 @@ error_enum!(
     ConditionTypeMismatch,
     UnknownType,
     UnknownAnnotation,
+    LiteralOutOfRange,
+    InvalidTest,
     NoFunction,
     ReturnOutsideFunction,
     MissingReturnValue,
@@ -46,6 +46,21 @@ pub trait FindDeclarationHere {
         let _ = ty;
         vec![]
     }
+
+    /// Get names of variables without checking parent context
+    fn variable_names_here(&self) -> Vec<Name> {
+        vec![]
+    }
+
+    /// Get names of types without checking parent context
+    fn type_names_here(&self) -> Vec<Name> {
+        vec![]
+    }
+
+    /// Get name formats of functions without checking parent context
+    fn function_formats_here(&self) -> Vec<String> {
+        vec![]
+    }
 }
 
 /// Trait to find declaration at current level or above
@@ -111,6 +126,38 @@ pub trait FindDeclaration: FindDeclarationHere {
             .collect()
     }
 
+    /// Get names of all visible variables
+    fn variable_names(&self) -> Vec<Name> {
+        self.variable_names_here()
+            .into_iter()
+            .chain(
+                self.parent()
+                    .map(|p| p.variable_names())
+                    .unwrap_or_default(),
+            )
+            .collect()
+    }
+
+    /// Get names of all visible types
+    fn type_names(&self) -> Vec<Name> {
+        self.type_names_here()
+            .into_iter()
+            .chain(self.parent().map(|p| p.type_names()).unwrap_or_default())
+            .collect()
+    }
+
+    /// Get name formats of all visible functions
+    fn function_formats(&self) -> Vec<String> {
+        self.function_formats_here()
+            .into_iter()
+            .chain(
+                self.parent()
+                    .map(|p| p.function_formats())
+                    .unwrap_or_default(),
+            )
+            .collect()
+    }
+
     /// Get specialized type for generic type
     fn get_specialized(&self, generic: Type) -> Option<Type> {
         self.parent().map(|p| p.get_specialized(generic)).flatten()
@@ -211,6 +258,18 @@ impl FindDeclarationHere for ModuleData {
             })
             .collect()
     }
+
+    fn variable_names_here(&self) -> Vec<Name> {
+        self.variables.keys().cloned().collect()
+    }
+
+    fn type_names_here(&self) -> Vec<Name> {
+        self.types.keys().cloned().collect()
+    }
+
+    fn function_formats_here(&self) -> Vec<String> {
+        self.functions.keys().cloned().collect()
+    }
 }
 
 impl FindDeclaration for ModuleData {}
@@ -239,4 +298,16 @@ impl<M: AsRef<ModuleData>> FindDeclarationHere for M {
     fn traits_for_here(&self, ty: Class) -> Vec<Trait> {
         self.as_ref().traits_for_here(ty)
     }
+
+    fn variable_names_here(&self) -> Vec<Name> {
+        self.as_ref().variable_names_here()
+    }
+
+    fn type_names_here(&self) -> Vec<Name> {
+        self.as_ref().type_names_here()
+    }
+
+    fn function_formats_here(&self) -> Vec<String> {
+        self.as_ref().function_formats_here()
+    }
 }
@@ -43,3 +43,6 @@ pub use replace_self::*;
 
 mod link_impls;
 pub use link_impls::*;
+
+mod suggestions;
+pub use suggestions::*;
@@ -0,0 +1,128 @@
+use crate::{compilation::Compiler, hir::ModuleData, named::Named};
+
+/// Levenshtein distance between two strings
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, a) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, b) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if a == *b {
+                diagonal
+            } else {
+                1 + diagonal.min(above).min(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+/// Find candidate, that is the most similar to `name`.
+///
+/// Candidates, that differ from `name` in more than a third of characters,
+/// are not considered similar
+pub fn closest<'c>(name: &str, candidates: impl IntoIterator<Item = &'c str>) -> Option<&'c str> {
+    let max_distance = name.chars().count().max(3) / 3;
+    candidates
+        .into_iter()
+        .filter(|candidate| *candidate != name)
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Find `use` statement, that imports `item` from module, satisfying `contains`.
+///
+/// Modules of current package are checked first, then its dependencies and `ppl`
+pub fn import_for(
+    compiler: &Compiler,
+    item: &str,
+    contains: impl Fn(&ModuleData) -> bool,
+) -> Option<String> {
+    let current_module = compiler.current_module();
+    let current_package = compiler
+        .packages
+        .get_index(compiler.current_package().index())
+        .map(|(_, package)| package);
+
+    let local = current_package
+        .into_iter()
+        .flat_map(|package| package.modules.iter())
+        .filter(|module| **module != current_module)
+        .map(|module| module.data(compiler))
+        .find(|module| contains(module))
+        .map(|module| format!("use {}.{item}", module.name()));
+    if local.is_some() {
+        return local;
+    }
+
+    current_package
+        .into_iter()
+        .flat_map(|package| package.dependencies.iter())
+        .map(|package| package.data(compiler))
+        .chain(compiler.packages.get("ppl"))
+        .filter(|package| {
+            package
+                .modules
+                .first()
+                .is_some_and(|module| *module != current_module && contains(module.data(compiler)))
+        })
+        .map(|package| format!("use {}.{item}", package.name))
+        .next()
+}
+
+/// Help message for name, that isn't visible in current context.
+///
+/// Prefers importing declaration with the same name over similar visible names
+pub fn suggestion(name: &str, visible: &[String], import: Option<String>) -> Option<String> {
+    if let Some(import) = import {
+        return Some(format!("consider importing it with `{import}`"));
+    }
+
+    closest(name, visible.iter().map(String::as_str))
+        .map(|similar| format!("did you mean `{similar}`?"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distance() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("name", ""), 4);
+        assert_eq!(edit_distance("name", "name"), 0);
+        assert_eq!(edit_distance("nmae", "name"), 2);
+        assert_eq!(edit_distance("prnt <>", "print <>"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn closest_name() {
+        let names = ["greeting", "greet <>", "Integer", "String"];
+        assert_eq!(closest("greting", names), Some("greeting"));
+        assert_eq!(closest("Strng", names), Some("String"));
+        assert_eq!(closest("gret <>", names), Some("greet <>"));
+        assert_eq!(closest("String", names), None);
+        assert_eq!(closest("x", names), None);
+        assert_eq!(closest("Boolean", names), None);
+    }
+
+    #[test]
+    fn suggestion_message() {
+        let visible = vec!["greeting".to_string()];
+        assert_eq!(
+            suggestion("greting", &visible, None).as_deref(),
+            Some("did you mean `greeting`?")
+        );
+        assert_eq!(
+            suggestion("greting", &visible, Some("use strings.greting".into())).as_deref(),
+            Some("consider importing it with `use strings.greting`")
+        );
+        assert_eq!(suggestion("unrelated", &visible, None), None);
+    }
+}
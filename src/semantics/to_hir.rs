@@ -22,8 +22,8 @@ use crate::syntax::{Identifier, Keyword, Ranged};
 use crate::{AddSourceLocation, ErrVec, SourceLocation, WithSourceLocation};
 
 use super::{
-    error::*, AddDeclaration, Context, Convert, ConvertibleTo, Declare, FindDeclaration,
-    GenericContext, Implicit, ModuleContext,
+    error::*, import_for, suggestion, AddDeclaration, Context, Convert, ConvertibleTo, Declare,
+    FindDeclaration, GenericContext, Implicit, ModuleContext,
 };
 use crate::ast::{self, CallNamePart, FnKind, If};
 use crate::semantics::monomorphize::Monomorphize;
@@ -79,16 +79,68 @@ impl ToHIR for ast::Literal {
                 value: *value,
                 ty: context.builtin().types().bool(),
             },
-            ast::Literal::Integer { value, .. } => hir::Literal::Integer {
-                span: self.range(),
-                value: value.parse::<rug::Integer>().unwrap(),
-                ty: context.builtin().types().integer(),
-            },
-            ast::Literal::Rational { value, .. } => hir::Literal::Rational {
-                span: self.range(),
-                value: rug::Rational::from_decimal(&value).unwrap(),
-                ty: context.builtin().types().rational(),
-            },
+            ast::Literal::Integer { value, suffix, .. } => {
+                let value = value.parse::<rug::Integer>().unwrap();
+                let Some(suffix) = suffix else {
+                    return Ok(hir::Literal::Integer {
+                        span: self.range(),
+                        value,
+                        ty: context.builtin().types().integer(),
+                    });
+                };
+
+                let ty = context.builtin().types().for_suffix(suffix);
+                let number = ty.fixed_width().unwrap();
+                if !number.can_represent(&value) {
+                    return Err(LiteralOutOfRange {
+                        value: value.to_string(),
+                        ty,
+                        at: self.range().into(),
+                    }
+                    .into());
+                }
+
+                if number.is_float() {
+                    hir::Literal::Rational {
+                        span: self.range(),
+                        value: value.into(),
+                        ty,
+                    }
+                } else {
+                    hir::Literal::Integer {
+                        span: self.range(),
+                        value,
+                        ty,
+                    }
+                }
+            }
+            ast::Literal::Rational { value, suffix, .. } => {
+                let rational = rug::Rational::from_decimal(&value).unwrap();
+                let Some(suffix) = suffix else {
+                    return Ok(hir::Literal::Rational {
+                        span: self.range(),
+                        value: rational,
+                        ty: context.builtin().types().rational(),
+                    });
+                };
+
+                let ty = context.builtin().types().for_suffix(suffix);
+                let number = ty.fixed_width().unwrap();
+                if !number.can_represent_rational(&rational) {
+                    return Err(LiteralOutOfRange {
+                        value: value.clone(),
+                        ty,
+                        at: self.range().into(),
+                    }
+                    .into());
+                }
+
+                hir::Literal::Rational {
+                    span: self.range(),
+                    value: rational,
+                    ty,
+                }
+            }
             ast::Literal::String { value, .. } => hir::Literal::String {
                 span: self.range(),
                 value: value.clone(),
@@ -105,9 +157,12 @@ impl ToHIR for ast::VariableReference {
     fn to_hir(&self, context: &mut impl Context) -> Result<Self::HIR, Self::Error> {
         let var = context.find_variable(&self.name);
         if var.is_none() {
+            let name = self.name.as_str();
+            let import = import_for(context.compiler(), name, |m| m.variables.contains_key(name));
             return Err(UndefinedVariable {
                 name: self.name.clone().to_string(),
                 at: self.name.range().into(),
+                help: suggestion(name, &context.variable_names(), import),
             }
             .into());
         }
@@ -270,17 +325,64 @@ impl ToHIR for ast::Call {
             self.name_parts[1].range()
         };
 
+        let help = if candidates_not_viable.is_empty() {
+            call_suggestion(self, &args_cache, context)
+        } else {
+            None
+        };
+
         Err(NoFunction {
             kind: self.kind,
             name,
             arguments,
             candidates: candidates_not_viable,
             at: at.into(),
+            help,
         }
         .into())
     }
 }
 
+/// Suggest fix for call, that has no functions with the same name format
+fn call_suggestion(
+    call: &ast::Call,
+    args_cache: &[Option<hir::Expression>],
+    context: &impl Context,
+) -> Option<String> {
+    let parts: Vec<&str> = call
+        .name_parts
+        .iter()
+        .zip(args_cache)
+        .map(|(part, arg)| match part {
+            CallNamePart::Text(text) if arg.is_none() => text.as_str(),
+            _ => "<>",
+        })
+        .collect();
+    let formats = context.function_formats();
+
+    // Misspelled or not imported variable, passed as an argument
+    for (i, part) in parts.iter().enumerate() {
+        if *part == "<>" {
+            continue;
+        }
+
+        let mut with_argument = parts.clone();
+        with_argument[i] = "<>";
+        if !formats.contains(&with_argument.join(" ")) {
+            continue;
+        }
+
+        let import = import_for(context.compiler(), part, |m| m.variables.contains_key(*part));
+        if let Some(help) = suggestion(part, &context.variable_names(), import) {
+            return Some(help);
+        }
+    }
+
+    let format = parts.join(" ");
+    let import = import_for(context.compiler(), "*", |m| m.functions.contains_key(&format));
+    suggestion(&format, &formats, import)
+}
+
 impl ToHIR for ast::Tuple {
     type HIR = hir::Expression;
 
@@ -306,9 +408,11 @@ impl ToHIR for ast::TypeReference {
 
         let ty = context.find_type(name);
         if ty.is_none() {
+            let import = import_for(context.compiler(), name, |m| m.types.contains_key(name));
             return Err(UnknownType {
                 name: self.name.clone().to_string(),
                 at: self.name.range().into(),
+                help: suggestion(name, &context.type_names(), import),
             }
             .into());
         }
@@ -589,6 +693,7 @@ impl ToHIR for ast::Annotation {
                 }
             }
             "builtin" if self.args.is_empty() => return Ok(hir::Annotation::Builtin),
+            "test" if self.args.is_empty() => return Ok(hir::Annotation::Test),
             _ => {}
         }
         Err(UnknownAnnotation {
@@ -784,6 +889,16 @@ impl ToHIR for ast::Use {
                 .clone()
         };
 
+        let compiler = context.compiler_mut();
+        let current_module = compiler.current_module();
+        if current_module != module {
+            compiler
+                .imports
+                .entry(current_module)
+                .or_default()
+                .insert(module);
+        }
+
         let name = self.path.last().unwrap().as_str();
 
         let mut functions = IndexMap::new();
@@ -809,10 +924,18 @@ impl ToHIR for ast::Use {
             set.insert(f.name().to_string(), f.clone().into());
             f.clone().into()
         } else {
+            let items: Vec<_> = module
+                .variables
+                .keys()
+                .chain(module.types.keys())
+                .cloned()
+                .chain(module.iter_functions().map(|f| f.name().to_string()))
+                .collect();
             return Err::<_, Self::Error>(
                 UnresolvedImport {
                     name: name.to_string(),
                     at: self.path.last().unwrap().range().into(),
+                    help: suggestion(name, &items, None),
                 }
                 .into(),
             );
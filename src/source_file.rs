@@ -59,6 +59,11 @@ impl SourceFile {
         self.source.name()
     }
 
+    /// Contents of the source file
+    pub fn contents(&self) -> &str {
+        self.source.inner()
+    }
+
     /// Line number for byte index
     pub fn line_number(&self, offset: usize) -> LineNumber {
         let str = self.source.inner();
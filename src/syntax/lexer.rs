@@ -478,9 +478,17 @@ impl Lexer for FullSourceLexer<'_> {
     /// Skip indentation.
     /// Changes current indentation level to the amount of tabs skipped
     fn skip_indentation(&mut self) -> &mut Self {
-        while self.peek() == Some(Token::Tab) {
+        loop {
+            while self.peek() == Some(Token::Tab) {
+                self.next();
+                self.indentation += 1;
+            }
+
+            // Lines with only tabs or comments don't change indentation
+            if self.indentation == 0 || self.peek() != Some(Token::Newline) {
+                break;
+            }
             self.next();
-            self.indentation += 1;
         }
         self
     }
@@ -690,7 +698,7 @@ impl<F: Fn() -> String> Lexer for InteractiveLexer<F> {
 mod tests {
     use crate::syntax::Lexer;
 
-    use super::InteractiveLexer;
+    use super::{FullSourceLexer, InteractiveLexer};
 
     #[test]
     fn correct_peek_after_skipping_newlines() {
@@ -717,4 +725,24 @@ mod tests {
         assert_eq!(lexer.peek_slice(), "x");
         assert_eq!(lexer.peek_span(), 2..3);
     }
+
+    #[test]
+    fn skip_lines_with_only_comments_in_indentation() {
+        let mut lexer = FullSourceLexer::new("\t// comment\n\t\n\tx");
+
+        lexer.skip_indentation();
+        assert_eq!(lexer.indentation(), 1);
+        assert_eq!(lexer.peek(), Some(super::Token::Id));
+        assert_eq!(lexer.peek_slice(), "x");
+    }
+
+    #[test]
+    fn doc_comments_are_tokens() {
+        let mut lexer = FullSourceLexer::new("/// Docs\n//// Not docs\nx");
+
+        assert_eq!(lexer.next(), Some(super::Token::DocComment));
+        assert_eq!(lexer.slice(), "/// Docs");
+        assert_eq!(lexer.next(), Some(super::Token::Newline));
+        assert_eq!(lexer.next(), Some(super::Token::Id));
+    }
 }
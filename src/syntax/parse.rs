@@ -109,6 +109,23 @@ impl<Lexer: super::Lexer> Context<Lexer> {
     pub fn no_space_before_next_token(&mut self) -> bool {
         !self.has_space_before_next_token()
     }
+
+    /// Parse documentation comment (`///` lines) before declaration.
+    /// Returns `None`, if there is no documentation comment
+    pub fn parse_doc_comment(&mut self) -> Option<String> {
+        let mut lines = Vec::new();
+        while self.lexer.consume(Token::DocComment).is_ok() {
+            let text = &self.lexer.slice().trim_end()["///".len()..];
+            lines.push(text.strip_prefix(' ').unwrap_or(text).to_string());
+
+            // Documented item starts on the next line
+            while matches!(self.lexer.peek(), Some(Token::Newline | Token::Tab)) {
+                self.lexer.next();
+            }
+        }
+
+        (!lines.is_empty()).then(|| lines.join("\n"))
+    }
 }
 
 impl<'l, Lexer: super::Lexer> Context<Lexer> {
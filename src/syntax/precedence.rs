@@ -67,6 +67,11 @@ impl Default for PrecedenceGroups {
                     name: "ComparisonPrecedence".to_string(),
                     associativity: Associativity::Chain,
                 },
+                // Shifts bind weaker than arithmetic, like in C and Rust
+                PrecedenceGroup {
+                    name: "BitwiseShiftPrecedence".to_string(),
+                    associativity: Associativity::Left,
+                },
                 PrecedenceGroup {
                     name: "AdditionPrecedence".to_string(),
                     associativity: Associativity::Left,
@@ -87,15 +92,42 @@ impl Default for PrecedenceGroups {
                 (">".to_string(), 1),
                 ("<=".to_string(), 1),
                 (">=".to_string(), 1),
-                ("+".to_string(), 2),
-                ("-".to_string(), 2),
-                ("*".to_string(), 3),
-                ("/".to_string(), 3),
-                ("%".to_string(), 3),
-                ("^".to_string(), 4),
+                ("<<".to_string(), 2),
+                (">>".to_string(), 2),
+                ("+".to_string(), 3),
+                ("-".to_string(), 3),
+                ("+%".to_string(), 3),
+                ("-%".to_string(), 3),
+                ("+|".to_string(), 3),
+                ("-|".to_string(), 3),
+                ("*".to_string(), 4),
+                ("/".to_string(), 4),
+                ("%".to_string(), 4),
+                ("*%".to_string(), 4),
+                ("*|".to_string(), 4),
+                ("^".to_string(), 5),
             ]
             .into_iter()
             .collect(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::PrecedenceGroups;
+
+    #[test]
+    fn shifts_bind_weaker_than_arithmetic() {
+        let groups = PrecedenceGroups::default();
+
+        // 1 << 2 + 3 == 1 << (2 + 3)
+        assert!(groups.has_greater_precedence("+", "<<"));
+        // 1 + 2 << 3 == (1 + 2) << 3
+        assert!(groups.has_less_precedence("<<", "+"));
+        assert!(groups.has_less_precedence("<<", "*"));
+        assert!(groups.has_greater_precedence("<<", "=="));
+        // 1 << 2 >> 3 == (1 << 2) >> 3
+        assert!(!groups.has_greater_precedence(">>", "<<"));
+    }
+}
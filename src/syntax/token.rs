@@ -67,8 +67,8 @@ pub enum Token {
     #[token("none")]
     None,
 
-    /// Integer literal
-    #[regex("[0-9]+")]
+    /// Integer literal with optional type suffix
+    #[regex("[0-9]+([iu](8|16|32|64)|f(32|64))?")]
     Integer,
 
     /// Assign token
@@ -111,6 +111,10 @@ pub enum Token {
     #[token("\n")]
     Newline,
 
+    /// Documentation comment line, starting with `///`
+    #[regex("///([^/\n][^\n]*)?")]
+    DocComment,
+
     /// ':' token
     #[token(":")]
     Colon,
@@ -211,8 +215,8 @@ pub enum Token {
     #[token("}")]
     RBrace,
 
-    /// Rational literal
-    #[regex("[0-9]*[.][0-9]+")]
+    /// Rational literal with optional type suffix
+    #[regex("[0-9]*[.][0-9]+(f(32|64))?")]
     Rational,
 
     /// "use" token
@@ -16,7 +16,11 @@ e2es! {
     empty_block,
     empty_constructor,
     escaped_id,
+    f64_math,
+    fixed_width,
     generics,
+    hash_map,
+    hash_set,
     import_all,
     integer,
     integer_not_eq_rational,
@@ -30,18 +34,22 @@ e2es! {
     multiple_initialization,
     non_class_constructor,
     plus_assign,
+    pointer,
     predeclare_function,
     predeclare_vars,
     rational,
+    rational_math,
     reference_mut,
     reference_to_literal,
     reference_to_none,
     references,
     specify_variable_ty,
     star,
+    stdin,
     store_ref,
     string,
     supertraits,
+    text,
     traits,
     type_as_value,
     swap,
@@ -76,3 +84,522 @@ fn ppl() {
     let ir = crate::e2e::internal::ir(&tmp, name, &dir);
     assert_snapshot!("ppl.ir", ir);
 }
+
+#[test]
+fn ppl_test() {
+    use std::path::Path;
+
+    use tempdir::TempDir;
+
+    let temp_dir = TempDir::new("ppl").unwrap();
+    let dir = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/src/tests/ppl_test"));
+
+    let (log, status) = crate::e2e::internal::test(temp_dir.path(), dir, &[]);
+    assert!(!status.success(), "{log}");
+    assert!(log.contains("test addition works ... ok"), "{log}");
+    assert!(log.contains("test subtraction is broken ... FAILED"), "{log}");
+    assert!(log.contains("Assertion failed: 2 - 1 should be 2"), "{log}");
+    assert!(log.contains("src/main.ppl:8:1"), "{log}");
+    assert!(log.contains("src/main.ppl:9:2"), "{log}");
+    assert!(!log.contains("Tests are not run by main"), "{log}");
+    assert!(log.contains("1 passed; 1 failed; 0 filtered out"), "{log}");
+
+    let (log, status) = crate::e2e::internal::test(temp_dir.path(), dir, &["addition"]);
+    assert!(status.success(), "{log}");
+    assert!(log.contains("1 passed; 0 failed; 1 filtered out"), "{log}");
+}
+
+#[test]
+fn ppl_run() {
+    use std::path::Path;
+
+    use tempdir::TempDir;
+
+    let temp_dir = TempDir::new("ppl").unwrap();
+    let dir = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/src/tests/arguments"));
+
+    let (output, status) =
+        crate::e2e::internal::run_with_args(temp_dir.path(), dir, &["first", "second"]);
+    assert_eq!(status.code(), Some(2), "{output}");
+    assert!(output.contains("[first, second]\n"), "{output}");
+
+    let (output, status) = crate::e2e::internal::run_with_args(temp_dir.path(), dir, &[]);
+    assert!(status.success(), "{output}");
+    assert!(output.contains("[]\n"), "{output}");
+}
+
+#[test]
+fn release() {
+    use std::path::Path;
+
+    use tempdir::TempDir;
+
+    use crate::driver::commands::compile::OutputType;
+
+    let temp_dir = TempDir::new("ppl").unwrap();
+    let debug = temp_dir.path().join("debug");
+    let release = temp_dir.path().join("release");
+    let name = "integer";
+    let dir = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/src/tests/integer"));
+
+    crate::e2e::internal::compile_with_args(&debug, dir, &[]);
+    crate::e2e::internal::compile_with_args(&release, dir, &["--release", "--build-runtime"]);
+
+    let exe = OutputType::Executable.named(name);
+    assert_ne!(
+        std::fs::read(debug.join(&exe)).unwrap(),
+        std::fs::read(release.join(&exe)).unwrap(),
+        "--release build is the same as debug one"
+    );
+
+    let (debug_output, debug_status) = crate::e2e::internal::run(&debug, name, dir);
+    let (release_output, release_status) = crate::e2e::internal::run(&release, name, dir);
+    assert!(debug_status.success(), "{debug_output}");
+    assert!(release_status.success(), "{release_output}");
+    assert_eq!(debug_output, release_output);
+}
+
+#[test]
+fn incremental_rebuild() {
+    use std::{collections::HashMap, fs, time::SystemTime};
+
+    use tempdir::TempDir;
+
+    use crate::compilation::Cache;
+
+    let temp_dir = TempDir::new("ppl").unwrap();
+    let root = TempDir::new("ppl_incremental").unwrap();
+    let package = root.path().join("incremental");
+    let src = package.join("src");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(src.join("names.ppl"), "fn world => \"World\"").unwrap();
+    fs::write(
+        src.join("greet.ppl"),
+        "use names.*\n\nfn greet => println \"Hello, \" + world",
+    )
+    .unwrap();
+    fs::write(src.join("answer.ppl"), "fn answer => 42").unwrap();
+    fs::write(
+        src.join("main.ppl"),
+        "use greet.*\nuse answer.*\n\ngreet\nprintln answer",
+    )
+    .unwrap();
+
+    // Artifacts with time of their last modification
+    let cache = temp_dir.path().join(Cache::DIR).join("host");
+    let artifacts = || -> HashMap<String, SystemTime> {
+        fs::read_dir(&cache)
+            .unwrap()
+            .map(|entry| {
+                let entry = entry.unwrap();
+                let modified = entry.metadata().unwrap().modified().unwrap();
+                (entry.file_name().into_string().unwrap(), modified)
+            })
+            .collect()
+    };
+
+    crate::e2e::internal::compile_with_args(temp_dir.path(), &package, &[]);
+    let built = artifacts();
+    assert!(!built.is_empty());
+
+    crate::e2e::internal::compile_with_args(temp_dir.path(), &package, &[]);
+    assert_eq!(artifacts(), built, "unchanged modules were regenerated");
+
+    fs::write(src.join("names.ppl"), "fn world => \"Everyone\"").unwrap();
+    crate::e2e::internal::compile_with_args(temp_dir.path(), &package, &[]);
+    // Artifacts are named `{module}-{hash}...`
+    let mut regenerated: Vec<_> = artifacts()
+        .into_iter()
+        .filter(|(artifact, modified)| built.get(artifact) != Some(modified))
+        .map(|(artifact, _)| artifact.split('-').next().unwrap().to_string())
+        .collect();
+    regenerated.sort();
+    assert_eq!(regenerated, ["greet", "main", "names"]);
+}
+
+#[test]
+fn small_integers() {
+    use std::path::Path;
+
+    use tempdir::TempDir;
+
+    let temp_dir = TempDir::new("ppl").unwrap();
+    let dir = Path::new(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/src/tests/small_integers"
+    ));
+
+    let (output, status) = crate::e2e::internal::run_with_args(temp_dir.path(), dir, &[]);
+    assert!(status.success(), "{output}");
+    assert_eq!(
+        output,
+        "4611686018427387904\n\
+         -4611686018427387905\n\
+         21267647932558653957237540927630737409\n\
+         true\n\
+         true\n\
+         true\n\
+         1000\n"
+    );
+}
+
+#[test]
+fn fixed_width_overflow() {
+    use std::path::Path;
+
+    use tempdir::TempDir;
+
+    let temp_dir = TempDir::new("ppl").unwrap();
+    let dir = Path::new(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/src/tests/fixed_width"
+    ));
+
+    let (output, status) = crate::e2e::internal::run_with_args(temp_dir.path(), dir, &["overflow"]);
+    assert!(!status.success(), "{output}");
+    assert!(output.contains("Overflow in `I8 + I8`"), "{output}");
+
+    let (output, status) = crate::e2e::internal::run_with_args(temp_dir.path(), dir, &["shift"]);
+    assert!(!status.success(), "{output}");
+    assert!(output.contains("Overflow in `U8 << U8`"), "{output}");
+}
+
+#[test]
+fn fs() {
+    use std::path::Path;
+
+    use tempdir::TempDir;
+
+    let temp_dir = TempDir::new("ppl").unwrap();
+    let root = TempDir::new("ppl_fs").unwrap();
+    let dir = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/src/tests/fs"));
+
+    let (output, status) =
+        crate::e2e::internal::run_with_args(temp_dir.path(), dir, &[root.path().to_str().unwrap()]);
+    assert!(status.success(), "{output}");
+    assert_eq!(
+        output,
+        "true\n\
+         true\n\
+         true\n\
+         first\n\
+         second\n\
+         true\n\
+         true\n\
+         opened\n\
+         appended\n\
+         true\n\
+         true\n\
+         true\n\
+         txt\n\
+         notes.txt\n\
+         data\n\
+         true\n\
+         13\n\
+         true\n\
+         false\n\
+         1\n\
+         notes.txt\n\
+         true\n\
+         true\n\
+         true\n\
+         true\n\
+         false\n"
+    );
+}
+
+#[test]
+fn ppl_check() {
+    use std::path::Path;
+
+    let tests = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/src/tests"));
+
+    let dir = tests.join("multifile");
+    let (log, status) = crate::e2e::internal::check(&dir);
+    assert!(status.success(), "{log}");
+    assert!(!dir.join("target").exists());
+
+    let (log, status) = crate::e2e::internal::check(&tests.join("multiple_errors"));
+    assert!(!status.success(), "{log}");
+    assert!(log.contains("semantics::undefined_variable"), "{log}");
+    assert!(log.contains("semantics::no_member"), "{log}");
+}
+
+#[test]
+fn message_format_json() {
+    use std::path::Path;
+
+    let tests = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/src/tests"));
+
+    let (diagnostics, status) = crate::e2e::internal::check_json(&tests.join("multiple_errors"));
+    assert!(!status.success());
+    let codes: Vec<_> = diagnostics.iter().map(|d| d["code"].clone()).collect();
+    assert!(
+        codes.contains(&"semantics::undefined_variable".into()),
+        "{diagnostics:#?}"
+    );
+    assert!(
+        codes.contains(&"semantics::no_member".into()),
+        "{diagnostics:#?}"
+    );
+
+    let (diagnostics, status) =
+        crate::e2e::internal::check_json(&tests.join("candidate_not_viable"));
+    assert!(!status.success());
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:#?}");
+
+    let error = &diagnostics[0];
+    assert_eq!(error["code"], "semantics::no_function");
+    assert_eq!(error["severity"], "error");
+    assert_eq!(error["message"], "no operator `<:Rational> + <:Integer>`");
+
+    let labels = &error["labels"];
+    assert_eq!(labels[0]["label"], "for this operator");
+    assert_eq!(labels[0]["file"], "main.ppl");
+    assert_eq!(labels[0]["line"], 1);
+    assert_eq!(labels[0]["column"], 5);
+    assert_eq!(labels[1]["label"], "<:Rational>");
+    assert_eq!(labels[1]["column"], 1);
+
+    let candidate = &error["related"][0];
+    assert_eq!(candidate["severity"], "advice");
+    assert_eq!(candidate["message"], "candidate is not viable");
+    assert_eq!(candidate["source"]["code"], "semantics::type_mismatch");
+}
+
+#[test]
+fn did_you_mean() {
+    use std::path::Path;
+
+    let dir = Path::new(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/src/tests/did_you_mean"
+    ));
+    let (diagnostics, status) = crate::e2e::internal::check_json(dir);
+    assert!(!status.success());
+
+    let help = |message: &str| {
+        diagnostics
+            .iter()
+            .find(|d| d["message"] == message)
+            .unwrap_or_else(|| panic!("no `{message}` in {diagnostics:#?}"))["help"]
+            .clone()
+    };
+    assert_eq!(
+        help("no function `println greting`"),
+        "did you mean `greeting`?"
+    );
+    assert_eq!(
+        help("no function `prnt <:String>`"),
+        "did you mean `print <>`?"
+    );
+    assert_eq!(help("unknown type `Strng`"), "did you mean `String`?");
+    assert_eq!(
+        help("variable `farewell` is not defined"),
+        "consider importing it with `use names.farewell`"
+    );
+}
+
+#[test]
+fn repl() {
+    use tempdir::TempDir;
+
+    let temp_dir = TempDir::new("ppl").unwrap();
+    let input = [
+        "let x = 1 + 2",
+        "x",
+        ":type x * 2",
+        "undefined",
+        "println \"still running\"",
+        ":reset",
+        ":type x",
+        ":quit",
+    ]
+    .join("\n");
+
+    let (output, status) = crate::e2e::internal::repl(temp_dir.path(), &input);
+    assert!(status.success(), "{output}");
+    assert!(output.contains("3\n"), "{output}");
+    assert!(output.contains("Integer\n"), "{output}");
+    assert!(
+        output.contains("variable `undefined` is not defined"),
+        "{output}"
+    );
+    assert!(output.contains("still running\n"), "{output}");
+    assert!(output.contains("variable `x` is not defined"), "{output}");
+    assert!(temp_dir.path().join("history").exists());
+}
+
+#[test]
+fn ppl_doc() {
+    use std::path::Path;
+
+    use tempdir::TempDir;
+
+    let temp_dir = TempDir::new("ppl").unwrap();
+    let dir = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/src/tests/ppl_doc"));
+
+    let (log, status) = crate::e2e::internal::doc(temp_dir.path(), dir, false);
+    assert!(status.success(), "{log}");
+
+    let docs = temp_dir.path().join("doc/ppl_doc");
+    let index = std::fs::read_to_string(docs.join("index.html")).unwrap();
+    assert!(
+        index.contains("<a href=\"shapes.html\">shapes</a>"),
+        "{index}"
+    );
+
+    let shapes = std::fs::read_to_string(docs.join("shapes.html")).unwrap();
+    assert!(
+        shapes.contains("<p>Square with side <code>side</code></p>"),
+        "{shapes}"
+    );
+    assert!(
+        shapes.contains(
+            "fn area of &lt;square: <a href=\"shapes.html#type.Square\">Square</a>&gt; -&gt; Integer"
+        ),
+        "{shapes}"
+    );
+    assert!(shapes.contains("<h4>Implementors</h4>"), "{shapes}");
+
+    let (log, status) = crate::e2e::internal::doc(temp_dir.path(), dir, true);
+    assert!(!status.success(), "{log}");
+    assert!(log.contains("running 2 doctests"), "{log}");
+    assert!(
+        log.contains("test shapes - area of <:Square> (1) ... ok"),
+        "{log}"
+    );
+    assert!(
+        log.contains("test shapes - double <:Integer> (1) ... FAILED"),
+        "{log}"
+    );
+    assert!(log.contains("double 2 should be 5"), "{log}");
+    assert!(log.contains("1 passed; 1 failed"), "{log}");
+}
+
+#[test]
+fn ppl_lsp() {
+    use std::path::Path;
+
+    use serde_json::{json, Value};
+
+    let dir = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/src/tests/ppl_lsp"));
+    let main = format!("file://{}/src/main.ppl", dir.display());
+    let error = format!("file://{}/src/error.ppl", dir.display());
+    let position = |id: i32, method: &str, uri: &str, line: u32, character: u32| {
+        json!({
+            "jsonrpc": "2.0", "id": id, "method": method,
+            "params": {
+                "textDocument": { "uri": uri },
+                "position": { "line": line, "character": character }
+            }
+        })
+    };
+    let open = |uri: &str, text: &str| {
+        json!({
+            "jsonrpc": "2.0", "method": "textDocument/didOpen",
+            "params": {
+                "textDocument": { "uri": uri, "languageId": "ppl", "version": 1, "text": text }
+            }
+        })
+    };
+
+    let responses = crate::e2e::internal::lsp(&[
+        json!({
+            "jsonrpc": "2.0", "id": 1, "method": "initialize",
+            "params": { "capabilities": {} }
+        }),
+        json!({ "jsonrpc": "2.0", "method": "initialized", "params": {} }),
+        open(&main, "use greeting.*\n\nlet who = \"World\"\ngreet who\n"),
+        open(&error, "let y = x\n"),
+        position(2, "textDocument/hover", &main, 3, 1),
+        position(3, "textDocument/definition", &main, 3, 1),
+        position(4, "textDocument/definition", &main, 3, 7),
+        position(5, "textDocument/completion", &main, 3, 3),
+        json!({
+            "jsonrpc": "2.0", "id": 6, "method": "textDocument/documentSymbol",
+            "params": { "textDocument": { "uri": main } }
+        }),
+        json!({ "jsonrpc": "2.0", "id": 7, "method": "shutdown" }),
+        json!({ "jsonrpc": "2.0", "method": "exit" }),
+    ]);
+
+    let response = |id: i32| -> &Value {
+        responses
+            .iter()
+            .find(|r| r["id"] == id)
+            .unwrap_or_else(|| panic!("no response for {id}: {responses:#?}"))
+    };
+    let diagnostics = |uri: &str| -> &Value {
+        &responses
+            .iter()
+            .find(|r| r["method"] == "textDocument/publishDiagnostics" && r["params"]["uri"] == uri)
+            .unwrap_or_else(|| panic!("no diagnostics for {uri}: {responses:#?}"))["params"]
+            ["diagnostics"]
+    };
+
+    assert!(response(1)["result"]["capabilities"]["hoverProvider"] == true);
+
+    assert_eq!(diagnostics(&main), &json!([]));
+    let errors = diagnostics(&error);
+    assert_eq!(
+        errors[0]["code"], "semantics::undefined_variable",
+        "{errors}"
+    );
+    assert_eq!(
+        errors[0]["range"],
+        json!({ "start": { "line": 0, "character": 8 }, "end": { "line": 0, "character": 9 } })
+    );
+
+    let hover = response(2)["result"]["contents"]["value"].as_str().unwrap();
+    assert!(hover.contains("fn greet <name: String> -> None"), "{hover}");
+    assert!(hover.contains("Greet someone by name"), "{hover}");
+
+    let definition = &response(3)["result"];
+    assert!(
+        definition["uri"]
+            .as_str()
+            .unwrap()
+            .ends_with("greeting.ppl"),
+        "{definition}"
+    );
+    assert_eq!(
+        definition["range"]["start"],
+        json!({ "line": 1, "character": 3 })
+    );
+
+    let definition = &response(4)["result"];
+    assert_eq!(definition["uri"], main.as_str());
+    assert_eq!(
+        definition["range"],
+        json!({ "start": { "line": 2, "character": 4 }, "end": { "line": 2, "character": 7 } })
+    );
+
+    let completions = response(5)["result"].as_array().unwrap();
+    assert!(
+        completions
+            .iter()
+            .any(|c| c["label"] == "greet" && c["detail"] == "greet <:String>"),
+        "{completions:#?}"
+    );
+
+    let symbols = response(6)["result"].as_array().unwrap();
+    assert_eq!(symbols.len(), 1, "{symbols:#?}");
+    assert_eq!(symbols[0]["name"], "who");
+}
+
+#[test]
+fn ppl_explain() {
+    use crate::e2e::internal::explain;
+
+    let (page, status) = explain("semantics::no_function");
+    assert!(status.success());
+    assert!(page.starts_with("# semantics::no_function\n"), "{page}");
+
+    let (short, status) = explain("no_function");
+    assert!(status.success());
+    assert_eq!(short, page);
+
+    let (_, status) = explain("semantics::no_such_code");
+    assert!(!status.success());
+}